@@ -0,0 +1,245 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::{CollapseError, CollapseMonitor, Reading, ResortWindow, Verdict};
+
+/// How urgent an alert is. Ordered, so `Severity::Critical > Severity::Warning`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Severity {
+    /// An isolated anomaly, worth a look in the morning.
+    Info,
+    /// Anomalies are piling up.
+    Warning,
+    /// An early warning, or anomalies one after another. Page someone.
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Thresholds that grade anomalies into a [`Severity`], for [`AlertTracker`].
+///
+/// Each failing reading is graded on its own and with the readings before it:
+/// * [`Severity::Info`] for an anomaly.
+/// * [`Severity::Warning`] while `warning_anomalies` or more of the last `warning_span` checked readings failed.
+/// * [`Severity::Critical`] for `critical_consecutive` or more failing readings in a row.
+/// * `early_warning` for [`CollapseError::MaxTooSmall`] and [`CollapseError::MinTooBig`], on a single reading.
+///
+/// The alert level rises as soon as a reading grades above it, but only falls after `clear_after` readings in a row
+/// grade below it, so one safe reading does not clear a page.
+///
+/// Nothing holds back a rise. Under the default policy a single early warning pages at once, since it means the mine
+/// is about to fail, and the page then holds for `clear_after` readings even if the warning was a transient spike.
+/// Sensors prone to spikes should lower `early_warning`, and leave paging to `critical_consecutive`.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{AlertPolicy, Severity};
+/// //Early warnings alone should not page this mine, only repeated failures.
+/// let policy = AlertPolicy { early_warning: Severity::Info, ..AlertPolicy::default() };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AlertPolicy {
+    pub warning_anomalies: usize,
+    pub warning_span: usize,
+    pub critical_consecutive: usize,
+    pub early_warning: Severity,
+    pub clear_after: usize,
+}
+
+impl Default for AlertPolicy {
+    /// Warning for 3 anomalies in 10 readings, Critical for 3 in a row or an early warning, cleared after 5 readings.
+    fn default() -> Self {
+        AlertPolicy {
+            warning_anomalies: 3,
+            warning_span: 10,
+            critical_consecutive: 3,
+            early_warning: Severity::Critical,
+            clear_after: 5,
+        }
+    }
+}
+
+/// A change of the alert level, the moment to page or to stand down.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Alert<T = u128> {
+    /// Index of the reading that changed the level.
+    pub index: usize,
+    /// The level before, `None` when there was no alert.
+    pub from: Option<Severity>,
+    /// The level after, `None` when the alert cleared.
+    pub to: Option<Severity>,
+    /// The anomaly that raised the level. `None` when the level fell.
+    pub cause: Option<CollapseError<T>>,
+}
+
+impl<T: Copy + fmt::Display> fmt::Display for Alert<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.to, &self.cause) {
+            (Some(to), Some(cause)) => write!(f, "{}: {}", to, cause),
+            (Some(to), None) => write!(f, "{}: Lowered at index {}.", to, self.index),
+            (None, _) => write!(f, "Cleared at index {}.", self.index),
+        }
+    }
+}
+
+/// Grades the verdicts of a checker into alerts, under an [`AlertPolicy`].
+///
+/// Feed it the result of every reading, from a [`CollapseMonitor`], a [`MineMonitor`](crate::MineMonitor) sensor or
+/// a [`TimedMonitor`](crate::TimedMonitor). Readings still warming up are ignored. Every error counts as an anomaly.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{AlertPolicy, AlertTracker, CollapseMonitor, Severity};
+/// let mut monitor = CollapseMonitor::new(3);
+/// let mut alerts = AlertTracker::new(AlertPolicy::default());
+/// for reading in [10, 11, 12, 21, 22, 5, 33, 39] {
+///     alerts.observe(&monitor.push(reading));
+/// }
+/// //5 is less than twice the smallest reading in the window, an early warning.
+/// assert_eq!(alerts.level(), Some(Severity::Critical));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AlertTracker {
+    policy: AlertPolicy,
+    level: Option<Severity>,
+    ///Whether each of the last `warning_span` checked readings failed.
+    recent: VecDeque<bool>,
+    anomalies: usize,
+    consecutive: usize,
+    below: usize,
+    index: usize,
+}
+
+impl AlertTracker {
+    /// Creates a tracker with no alert raised.
+    /// ### Panics
+    /// If `warning_anomalies` or `critical_consecutive` is zero, which would grade safe readings, or if
+    /// `warning_span` is smaller than `warning_anomalies`, which would never warn.
+    pub fn new(policy: AlertPolicy) -> Self {
+        assert!(
+            policy.warning_anomalies > 0 && policy.critical_consecutive > 0,
+            "Zero anomalies would raise an alert on safe readings."
+        );
+        assert!(
+            policy.warning_span >= policy.warning_anomalies,
+            "The warning span cannot hold the anomalies it needs."
+        );
+        AlertTracker {
+            policy,
+            level: None,
+            recent: VecDeque::with_capacity(policy.warning_span),
+            anomalies: 0,
+            consecutive: 0,
+            below: 0,
+            index: 0,
+        }
+    }
+
+    /// Grades the verdict of the next reading, and returns the change of level it caused, if any.
+    /// The index in the alert counts every reading observed so far, warming up or not, starting at zero.
+    pub fn observe<T: Copy>(
+        &mut self,
+        result: &Result<Verdict, CollapseError<T>>,
+    ) -> Option<Alert<T>> {
+        let index = self.index;
+        self.index += 1;
+        let failed = match result {
+            Ok(Verdict::Warming) => return None,
+            Ok(Verdict::Safe) => false,
+            Err(_) => true,
+        };
+        if self.recent.len() == self.policy.warning_span && self.recent.pop_front() == Some(true) {
+            self.anomalies -= 1;
+        }
+        self.recent.push_back(failed);
+        self.anomalies += usize::from(failed);
+        self.consecutive = if failed { self.consecutive + 1 } else { 0 };
+
+        let grade = self.grade(result.as_ref().err());
+        let from = self.level;
+        if grade > from {
+            self.level = grade;
+            self.below = 0;
+        } else if grade < from {
+            self.below += 1;
+            if self.below < self.policy.clear_after.max(1) {
+                return None;
+            }
+            self.level = grade;
+            self.below = 0;
+        } else {
+            self.below = 0;
+            return None;
+        }
+        Some(Alert {
+            index,
+            from,
+            to: self.level,
+            cause: match grade > from {
+                true => result.clone().err(),
+                false => None,
+            },
+        })
+    }
+
+    ///The severity of the latest reading on its own and with the readings before it.
+    fn grade<T>(&self, error: Option<&CollapseError<T>>) -> Option<Severity> {
+        let mut grade = error.map(|_| Severity::Info);
+        if let Some(CollapseError::MaxTooSmall { .. } | CollapseError::MinTooBig { .. }) = error {
+            grade = grade.max(Some(self.policy.early_warning));
+        }
+        if self.anomalies >= self.policy.warning_anomalies {
+            grade = grade.max(Some(Severity::Warning));
+        }
+        if self.consecutive >= self.policy.critical_consecutive {
+            grade = grade.max(Some(Severity::Critical));
+        }
+        grade
+    }
+
+    /// The current alert level, `None` when no alert is raised.
+    pub fn level(&self) -> Option<Severity> {
+        self.level
+    }
+
+    /// The policy this tracker grades by.
+    pub fn policy(&self) -> AlertPolicy {
+        self.policy
+    }
+}
+
+/// Runs the collapse check over a whole series and grades it under `policy`, for post-incident reviews of what the
+/// night shift would have been paged for.
+/// ### Arguments
+/// * `vector:` A vector
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// * `policy:` The thresholds to grade by.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_alerts, AlertPolicy, Severity};
+/// let vector = vec![10, 11, 12, 21, 22, 5, 33, 39, 72, 111, 183, 294, 477, 771];
+/// let policy = AlertPolicy { warning_span: 5, clear_after: 3, ..AlertPolicy::default() };
+/// let alerts = collapse_alerts(&vector, 3, policy);
+/// //5, 33 and 39 fail in a row, then the alert clears 3 safe readings later.
+/// let levels: Vec<_> = alerts.iter().map(|alert| (alert.index, alert.to)).collect();
+/// assert_eq!(levels, vec![(5, Some(Severity::Critical)), (10, None)]);
+/// ```
+/// ### Panics
+/// If `period` is zero, or as [`AlertTracker::new`].
+pub fn collapse_alerts<T: Reading>(
+    vector: &[T],
+    period: usize,
+    policy: AlertPolicy,
+) -> Vec<Alert<T>> {
+    let mut monitor = CollapseMonitor::<ResortWindow<T>>::with_strategy(period);
+    let mut tracker = AlertTracker::new(policy);
+    vector
+        .iter()
+        .filter_map(|&reading| tracker.observe(&monitor.push(reading)))
+        .collect()
+}
@@ -0,0 +1,384 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::parse_readings::parse_reading;
+use crate::{
+    parse_timestamp, CollapseError, ParseError, ParseErrorKind, Reading, Strategy, TimedMonitor,
+};
+
+/// A column of a CSV input, by position counting from 0, or by the name in the header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+/// Whether the first row of a CSV input names its columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Header {
+    /// The first row is a header when its value field is not a reading, or when a column is selected by name.
+    #[default]
+    Auto,
+    Present,
+    Absent,
+}
+
+/// How [`parse_csv`] reads logger output. The default reads `timestamp,sensor_id,value` rows, with or without a
+/// header.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{Column, CsvFormat};
+/// //Semicolon separated, with the reading in a column named "pressure_kpa".
+/// let format = CsvFormat {
+///     delimiter: ';',
+///     value: Column::Name("pressure_kpa".to_string()),
+///     ..CsvFormat::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvFormat {
+    pub delimiter: char,
+    pub header: Header,
+    pub timestamp: Column,
+    pub sensor: Column,
+    pub value: Column,
+}
+
+impl Default for CsvFormat {
+    fn default() -> Self {
+        CsvFormat {
+            delimiter: ',',
+            header: Header::Auto,
+            timestamp: Column::Index(0),
+            sensor: Column::Index(1),
+            value: Column::Index(2),
+        }
+    }
+}
+
+/// One reading from a logger, with when and where it was taken. The timestamp is kept as logged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Record<T = u128> {
+    pub timestamp: String,
+    pub sensor: String,
+    pub value: T,
+}
+
+/// Parses logger CSV output into records, in the order they were logged.
+///
+/// Blank lines and lines starting with `#` are skipped. Fields are trimmed, and can be quoted with `"`, with `""`
+/// for a quote inside. Values are read like [`parse_readings`](crate::parse_readings) reads a line, so they can use
+/// `_` separators and `0x` hex. Columns other than the three selected are ignored.
+/// ### Arguments
+/// * `text:` The whole input, such as the contents of a file.
+/// * `format:` The delimiter, header and columns to read.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{parse_csv, CsvFormat, Record};
+/// let text = "timestamp,sensor_id,value\n2024-03-01T06:00:00Z,B7,35\n2024-03-01T06:00:00Z,C2,0x14\n";
+/// let records = parse_csv::<u128>(text, &CsvFormat::default()).unwrap();
+/// assert_eq!(
+///     records[1],
+///     Record { timestamp: "2024-03-01T06:00:00Z".to_string(), sensor: "C2".to_string(), value: 20 }
+/// );
+/// ```
+/// ### Errors
+/// The first row that cannot be read, with the line and column of the offending character.
+pub fn parse_csv<T: Reading>(text: &str, format: &CsvFormat) -> Result<Vec<Record<T>>, ParseError> {
+    let mut rows = text
+        .lines()
+        .enumerate()
+        .map(|(line, content)| (line + 1, content))
+        .filter(|(_, content)| {
+            let content = content.trim_start();
+            !content.is_empty() && !content.starts_with('#')
+        })
+        .peekable();
+    let Some(&(first_line, first_content)) = rows.peek() else {
+        return Ok(vec![]);
+    };
+    let first = split_fields(first_line, first_content, format.delimiter)?;
+    let by_name = [&format.timestamp, &format.sensor, &format.value]
+        .iter()
+        .any(|column| matches!(column, Column::Name(_)));
+    let has_header = match format.header {
+        Header::Present => true,
+        Header::Absent => false,
+        Header::Auto => match format.value {
+            Column::Index(value) if !by_name => first
+                .get(value)
+                .is_some_and(|(_, field)| parse_reading::<T>(field.trim()).is_err()),
+            _ => true,
+        },
+    };
+    let resolve = |column: &Column| match column {
+        Column::Index(index) => Ok(*index),
+        Column::Name(name) => match has_header {
+            true => first.iter().position(|(_, field)| field.trim() == name),
+            false => None,
+        }
+        .ok_or(ParseError {
+            line: first_line,
+            column: 1,
+            kind: ParseErrorKind::UnknownColumn,
+        }),
+    };
+    let columns = [
+        resolve(&format.timestamp)?,
+        resolve(&format.sensor)?,
+        resolve(&format.value)?,
+    ];
+    if has_header {
+        rows.next();
+    }
+
+    let mut records = vec![];
+    for (line, content) in rows {
+        let fields = split_fields(line, content, format.delimiter)?;
+        //Columns are characters into the line, offsets are bytes.
+        let error = |offset: usize, kind| ParseError {
+            line,
+            column: content[..offset].chars().count() + 1,
+            kind,
+        };
+        let [timestamp, sensor, value] = columns.map(|column| {
+            fields
+                .get(column)
+                .ok_or(error(content.len(), ParseErrorKind::MissingField))
+        });
+        let (start, value) = value?;
+        let reading = value.trim();
+        let start = start + value.len() - value.trim_start().len();
+        let value = match reading.is_empty() {
+            true => Err(error(start, ParseErrorKind::NoDigits)),
+            false => parse_reading(reading).map_err(|(offset, kind)| error(start + offset, kind)),
+        }?;
+        records.push(Record {
+            timestamp: timestamp?.1.trim().to_string(),
+            sensor: sensor?.1.trim().to_string(),
+            value,
+        });
+    }
+    Ok(records)
+}
+
+///Fields of a row, each with the byte offset of its text into the row.
+fn split_fields(
+    line: usize,
+    row: &str,
+    delimiter: char,
+) -> Result<Vec<(usize, Cow<'_, str>)>, ParseError> {
+    let mut fields = vec![];
+    let mut start = 0;
+    loop {
+        let rest = &row[start..];
+        let quote = start + rest.len() - rest.trim_start().len();
+        let end = match row[quote..].strip_prefix('"') {
+            Some(quoted) => {
+                let mut text = String::new();
+                let mut chars = quoted.char_indices().peekable();
+                let close = loop {
+                    match chars.next() {
+                        Some((_, '"')) if chars.next_if(|&(_, c)| c == '"').is_some() => {
+                            text.push('"')
+                        }
+                        Some((offset, '"')) => break quote + 1 + offset + 1,
+                        Some((_, c)) => text.push(c),
+                        None => {
+                            return Err(ParseError {
+                                line,
+                                column: row[..quote].chars().count() + 1,
+                                kind: ParseErrorKind::UnterminatedQuote,
+                            })
+                        }
+                    }
+                };
+                fields.push((quote + 1, Cow::Owned(text)));
+                //Anything between the closing quote and the delimiter is dropped.
+                row[close..].find(delimiter).map(|end| close + end)
+            }
+            None => {
+                let end = rest.find(delimiter).map(|end| start + end);
+                fields.push((start, Cow::Borrowed(&row[start..end.unwrap_or(row.len())])));
+                end
+            }
+        };
+        match end {
+            Some(end) => start = end + delimiter.len_utf8(),
+            None => return Ok(fields),
+        }
+    }
+}
+
+/// A reading that failed the collapse check, with the timestamp and sensor to find it in the field.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Violation<T = u128> {
+    /// Position of the failing record in the records checked.
+    pub record: usize,
+    pub timestamp: String,
+    pub sensor: String,
+    /// The failure. Its `index` counts the readings of this sensor only.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub error: CollapseError<T>,
+}
+
+impl<T: Copy + fmt::Display> fmt::Display for Violation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Sensor {} at {}: {}",
+            self.sensor, self.timestamp, self.error
+        )
+    }
+}
+
+impl<T: Copy + fmt::Debug + fmt::Display> std::error::Error for Violation<T> {}
+
+/// Checks logged records for imminent collapse. Each sensor is its own series, checked against the `period`
+/// readings that sensor logged before, in the order of `records`.
+/// ### Arguments
+/// * `records:` Records in the order they were logged, such as from [`parse_csv`].
+/// * `period:` Determines the range `i..i+k`, where i+k is not included, of each sensor.
+/// * `strategy:` The implementation to run on each sensor.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check_records, parse_csv, CsvFormat, Strategy};
+/// let text = "06:00,B7,1\n06:00,C2,1\n06:01,B7,1\n06:01,C2,9\n06:02,B7,2\n06:02,C2,8\n06:03,B7,5\n";
+/// let records = parse_csv::<u128>(text, &CsvFormat::default()).unwrap();
+/// let violation = collapse_check_records(&records, 2, Strategy::Resort).unwrap_err();
+/// assert_eq!((violation.timestamp.as_str(), violation.sensor.as_str()), ("06:03", "B7"));
+/// assert_eq!(violation.error.index(), 3);
+/// ```
+/// ### Errors
+/// The violation logged first, over every sensor.
+pub fn collapse_check_records<T: Reading>(
+    records: &[Record<T>],
+    period: usize,
+    strategy: Strategy,
+) -> Result<(), Violation<T>> {
+    by_sensor(records)
+        .into_iter()
+        .filter_map(|(positions, readings)| {
+            let error = strategy.collapse_check(&readings, period).err()?;
+            Some(violation(records, &positions, error))
+        })
+        .min_by_key(|violation| violation.record)
+        .map_or(Ok(()), Err)
+}
+
+/// `collapse_scan` over logged records, every violation of every sensor, in the order they were logged.
+pub fn collapse_scan_records<T: Reading>(
+    records: &[Record<T>],
+    period: usize,
+    strategy: Strategy,
+) -> Vec<Violation<T>> {
+    let mut violations: Vec<Violation<T>> = by_sensor(records)
+        .into_iter()
+        .flat_map(|(positions, readings)| {
+            strategy
+                .collapse_scan(&readings, period)
+                .into_iter()
+                .map(move |error| violation(records, &positions, error))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    violations.sort_by_key(|violation| violation.record);
+    violations
+}
+
+/// `collapse_check_records`, with a window defined by duration, as [`TimedMonitor`] checks it. Timestamps are read
+/// with [`parse_timestamp`], and a record whose timestamp is not a time, or goes back from the one before it for the
+/// same sensor, is reported as [`CollapseError::BadTimestamp`].
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check_records_timed, parse_csv, CsvFormat};
+/// use std::time::Duration;
+/// //B7 logs twice as often, so a 2 second window holds 2 of its readings, but only 1 of C2's.
+/// let text = "0,B7,1\n0,C2,1\n1,B7,2\n2,B7,3\n2,C2,2\n3,B7,5\n4,C2,3\n";
+/// let records = parse_csv::<u128>(text, &CsvFormat::default()).unwrap();
+/// let violation = collapse_check_records_timed(&records, Duration::from_secs(2)).unwrap_err();
+/// assert_eq!((violation.timestamp.as_str(), violation.sensor.as_str()), ("4", "C2"));
+/// ```
+/// ### Errors
+/// The violation logged first, over every sensor.
+/// ### Panics
+/// If `window` is zero.
+pub fn collapse_check_records_timed<T: Reading>(
+    records: &[Record<T>],
+    window: Duration,
+) -> Result<(), Violation<T>> {
+    match timed_violations(records, window, true).into_iter().next() {
+        Some(violation) => Err(violation),
+        None => Ok(()),
+    }
+}
+
+/// `collapse_scan_records`, with a window defined by duration.
+/// ### Panics
+/// If `window` is zero.
+pub fn collapse_scan_records_timed<T: Reading>(
+    records: &[Record<T>],
+    window: Duration,
+) -> Vec<Violation<T>> {
+    timed_violations(records, window, false)
+}
+
+///Violations in the order they were logged. With `first_only`, each sensor stops at its first.
+fn timed_violations<T: Reading>(
+    records: &[Record<T>],
+    window: Duration,
+    first_only: bool,
+) -> Vec<Violation<T>> {
+    let mut violations = vec![];
+    for (positions, _) in by_sensor(records) {
+        let mut monitor = TimedMonitor::new(window);
+        for &position in &positions {
+            let record = &records[position];
+            let result = match parse_timestamp(&record.timestamp) {
+                Some(timestamp) => monitor.push(timestamp, record.value),
+                None => Err(monitor.skip(record.value)),
+            };
+            if let Err(error) = result {
+                violations.push(violation(records, &positions, error));
+                if first_only {
+                    break;
+                }
+            }
+        }
+    }
+    violations.sort_by_key(|violation| violation.record);
+    violations
+}
+
+///The positions and readings of each sensor, sensors in the order they first logged.
+fn by_sensor<T: Reading>(records: &[Record<T>]) -> Vec<(Vec<usize>, Vec<T>)> {
+    let mut sensors: HashMap<&str, usize> = HashMap::new();
+    let mut series: Vec<(Vec<usize>, Vec<T>)> = vec![];
+    for (c, record) in records.iter().enumerate() {
+        let sensor = *sensors.entry(&record.sensor).or_insert_with(|| {
+            series.push((vec![], vec![]));
+            series.len() - 1
+        });
+        series[sensor].0.push(c);
+        series[sensor].1.push(record.value);
+    } //O(n)
+    series
+}
+
+fn violation<T: Reading>(
+    records: &[Record<T>],
+    positions: &[usize],
+    error: CollapseError<T>,
+) -> Violation<T> {
+    let record = positions[error.index()];
+    Violation {
+        record,
+        timestamp: records[record].timestamp.clone(),
+        sensor: records[record].sensor.clone(),
+        error,
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt;
+
+/// Reasons a reading fails the collapse check.
+///
+/// Every variant carries the `index` of the offending reading in the input and the `target` reading itself.
+/// Most also carry the `min` and `max` of the window it was checked against, in the reading type `T`.
+/// Each [`WindowRule`](crate::WindowRule) reports its own variant.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check, CollapseError};
+/// let vector = vec![1, 1, 1, 4];
+/// let result = collapse_check(&vector, 3);
+/// assert_eq!(
+///     result,
+///     Err(CollapseError::MaxTooSmall { index: 3, target: 4, min: 1, max: 1 })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum CollapseError<T = u128> {
+    /// Early warning. Twice the window maximum is still below `target`, or `k` times it for [`KSum`](crate::KSum).
+    MaxTooSmall {
+        index: usize,
+        target: T,
+        min: T,
+        max: T,
+    },
+    /// Early warning. Twice the window minimum is already above `target`, or `k` times it for [`KSum`](crate::KSum).
+    MinTooBig {
+        index: usize,
+        target: T,
+        min: T,
+        max: T,
+    },
+    /// Normal warning. `target` is within range, but no pair in the window reaches it, or no `k` readings for
+    /// [`KSum`](crate::KSum).
+    NoPairFound {
+        index: usize,
+        target: T,
+        min: T,
+        max: T,
+    },
+    /// A floating point reading is NaN or infinite, so no pair can be compared with it.
+    /// Only the tolerant checks report it, before the reading is checked or enters the window.
+    NotFinite { index: usize, target: T },
+    /// [`WithinRange`](crate::WithinRange) failed. `target` is outside `[min, 2 * max]`.
+    OutOfRange {
+        index: usize,
+        target: T,
+        min: T,
+        max: T,
+    },
+    /// [`WithinDeviations`](crate::WithinDeviations) failed. `target` is too many standard deviations `std_dev`
+    /// from the window `mean`.
+    Outlier {
+        index: usize,
+        target: T,
+        mean: f64,
+        std_dev: f64,
+    },
+    /// A custom [`FnRule`](crate::FnRule) rejected `target`.
+    Rejected {
+        index: usize,
+        target: T,
+        min: T,
+        max: T,
+    },
+    /// Only the time-based checks report it. No reading was logged within the window before `target`, so there is
+    /// nothing to check it against. The reading still enters the window.
+    EmptyWindow { index: usize, target: T },
+    /// Only the time-based checks report it. The timestamp of `target` is earlier than the one before it, or is not a
+    /// time at all. The reading is not checked and does not enter the window.
+    BadTimestamp { index: usize, target: T },
+}
+
+impl<T: Copy> CollapseError<T> {
+    /// Position of the offending reading in the input.
+    pub fn index(&self) -> usize {
+        self.fields().0
+    }
+
+    /// The offending reading.
+    pub fn target(&self) -> T {
+        self.fields().1
+    }
+
+    /// `(min, max)` of the window the reading was checked against, for the variants that carry them.
+    pub fn window(&self) -> Option<(T, T)> {
+        self.fields().2
+    }
+
+    /// Name of the variant, for reports that list anomalies by kind.
+    /// ### Example
+    /// ```
+    /// use namt_preventative_collapse::collapse_check;
+    /// let error = collapse_check(&[1, 1, 1, 4], 3).unwrap_err();
+    /// assert_eq!(error.kind(), "MaxTooSmall");
+    /// ```
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CollapseError::MaxTooSmall { .. } => "MaxTooSmall",
+            CollapseError::MinTooBig { .. } => "MinTooBig",
+            CollapseError::NoPairFound { .. } => "NoPairFound",
+            CollapseError::NotFinite { .. } => "NotFinite",
+            CollapseError::OutOfRange { .. } => "OutOfRange",
+            CollapseError::Outlier { .. } => "Outlier",
+            CollapseError::Rejected { .. } => "Rejected",
+            CollapseError::EmptyWindow { .. } => "EmptyWindow",
+            CollapseError::BadTimestamp { .. } => "BadTimestamp",
+        }
+    }
+
+    ///`(index, target, Some((min, max)))`, shared by every variant. Variants without window bounds have `None`.
+    fn fields(&self) -> (usize, T, Option<(T, T)>) {
+        match self {
+            CollapseError::MaxTooSmall {
+                index,
+                target,
+                min,
+                max,
+            }
+            | CollapseError::MinTooBig {
+                index,
+                target,
+                min,
+                max,
+            }
+            | CollapseError::NoPairFound {
+                index,
+                target,
+                min,
+                max,
+            }
+            | CollapseError::OutOfRange {
+                index,
+                target,
+                min,
+                max,
+            }
+            | CollapseError::Rejected {
+                index,
+                target,
+                min,
+                max,
+            } => (*index, *target, Some((*min, *max))),
+            CollapseError::NotFinite { index, target }
+            | CollapseError::Outlier { index, target, .. }
+            | CollapseError::EmptyWindow { index, target }
+            | CollapseError::BadTimestamp { index, target } => (*index, *target, None),
+        }
+    }
+}
+
+impl<T: Copy + fmt::Display> fmt::Display for CollapseError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            CollapseError::MaxTooSmall { .. } => {
+                "Imminent Failure: Maximum is too small to reach the required sum."
+            }
+            CollapseError::MinTooBig { .. } => {
+                "Imminent Failure: Minimum is too big to reach the required sum."
+            }
+            CollapseError::NoPairFound { .. } => {
+                "Imminent Failure detected. No pairs reach the required sum."
+            }
+            CollapseError::NotFinite { .. } => "Invalid reading: Not a finite number.",
+            CollapseError::OutOfRange { .. } => {
+                "Imminent Failure: Reading is outside the range the window can reach."
+            }
+            CollapseError::Outlier { .. } => {
+                "Imminent Failure: Reading is too far from the window mean."
+            }
+            CollapseError::Rejected { .. } => {
+                "Imminent Failure: Reading rejected by a custom rule."
+            }
+            CollapseError::EmptyWindow { .. } => {
+                "Undecided: No readings were logged within the window."
+            }
+            CollapseError::BadTimestamp { .. } => {
+                "Invalid reading: Timestamp is out of order or not a time."
+            }
+        };
+        let (index, target, window) = self.fields();
+        write!(f, "{} Index: {}, Element: {}", message, index, target)?;
+        if let Some((min, max)) = window {
+            write!(f, ", Window min: {}, Window max: {}", min, max)?;
+        }
+        match self {
+            CollapseError::Outlier { mean, std_dev, .. } => {
+                write!(
+                    f,
+                    ", Window mean: {}, Standard deviation: {}",
+                    mean, std_dev
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<T: Copy + fmt::Debug + fmt::Display> std::error::Error for CollapseError<T> {}
+
+/// A line of input that is not a reading, from [`parse_readings`](crate::parse_readings) or
+/// [`parse_csv`](crate::parse_csv).
+///
+/// `line` and `column` count from 1, and `column` counts characters, so it points at the offending character in an
+/// editor.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{parse_readings, ParseError, ParseErrorKind};
+/// let result = parse_readings::<u128>("35\n  2O\n");
+/// assert_eq!(result, Err(ParseError { line: 2, column: 4, kind: ParseErrorKind::InvalidDigit }));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+/// Why a line is not a reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ParseErrorKind {
+    /// A character that is not a digit of the reading, a `_` separator, or a sign the reading type can hold.
+    InvalidDigit,
+    /// A sign, a `0x` prefix or separators, without any digits.
+    NoDigits,
+    /// The reading is past the range of the reading type.
+    Overflow,
+    /// A CSV row without the selected column.
+    MissingField,
+    /// A CSV column selected by a name that no header has.
+    UnknownColumn,
+    /// A quoted CSV field without its closing quote.
+    UnterminatedQuote,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self.kind {
+            ParseErrorKind::InvalidDigit => "Invalid digit in reading.",
+            ParseErrorKind::NoDigits => "Reading has no digits.",
+            ParseErrorKind::Overflow => "Reading is past the range of the reading type.",
+            ParseErrorKind::MissingField => "Row has no field in the selected column.",
+            ParseErrorKind::UnknownColumn => "No header names the selected column.",
+            ParseErrorKind::UnterminatedQuote => "Quoted field is never closed.",
+        };
+        write!(f, "Line {}, column {}: {}", self.line, self.column, message)
+    }
+}
+
+impl std::error::Error for ParseError {}
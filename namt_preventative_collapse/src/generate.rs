@@ -0,0 +1,344 @@
+use std::io::{self, Write};
+
+use crate::CollapseError;
+
+/// An anomaly the [`Generator`] injects, by the [`CollapseError`] it causes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Injection {
+    /// A spike past twice the largest reading of the window, reported as `MaxTooSmall`.
+    TooLarge,
+    /// A zero after a window without one, as from a sensor that drops out, reported as `MinTooBig`.
+    TooSmall,
+    /// An odd reading within range of a window of even readings, which no pair reaches, reported as `NoPairFound`.
+    NoPair,
+}
+
+/// Seeded generator of synthetic mine readings, with anomalies injected where asked, for tests and benchmarks that
+/// need more than the bundled inputs.
+///
+/// Valid readings are even and at most `max_reading`, and are copies, sums or differences of the readings in their
+/// window. Every window also holds a zero, as from a sensor at rest: without one, the early warnings force each
+/// reading to at least double the smallest reading of its window, and a long series outgrows any integer type. The one
+/// exception is the last reading before a `TooSmall` that follows closely on another anomaly, which doubles the
+/// smallest reading of its window and so reaches up to twice `max_reading`.
+/// The series only fails the collapse check at the injected anomalies, which are more than `period` readings apart,
+/// so each one is judged against a window of valid readings and reported exactly as the ground truth says.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_scan, Generator, Injection};
+/// let generator = Generator {
+///     anomalies: vec![(40, Injection::NoPair), (60, Injection::TooSmall)],
+///     random_anomalies: 2,
+///     ..Generator::new(5, 200)
+/// };
+/// let synthetic = generator.generate();
+/// assert_eq!(synthetic.readings.len(), 200);
+/// assert_eq!(synthetic.anomalies.len(), 4);
+/// assert_eq!(collapse_scan(&synthetic.readings, 5), synthetic.anomalies);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generator {
+    /// Readings each reading is checked against.
+    pub period: usize,
+    /// Number of readings.
+    pub len: usize,
+    /// The same seed and settings always give the same series.
+    pub seed: u64,
+    /// Largest valid reading, but for the exception above. Spikes go past it.
+    pub max_reading: u128,
+    /// Anomalies at chosen indexes.
+    pub anomalies: Vec<(usize, Injection)>,
+    /// Anomalies of random kinds at random indexes, on top of the chosen ones. Any that find no index far enough
+    /// from the others are left out.
+    pub random_anomalies: usize,
+}
+
+/// A generated series and its ground truth.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Synthetic {
+    pub readings: Vec<u128>,
+    /// Every anomaly in the series, by index, as `collapse_scan` reports it.
+    pub anomalies: Vec<CollapseError>,
+}
+
+impl Generator {
+    /// Largest `max_reading`. Spikes reach up to three times the largest reading, plus two, and must fit in a `u128`.
+    pub const MAX_READING: u128 = (u128::MAX - 2) / 3;
+
+    /// A generator of `len` readings for `period`, with seed 0, readings up to 1,000,000 and no anomalies.
+    pub fn new(period: usize, len: usize) -> Self {
+        Generator {
+            period,
+            len,
+            seed: 0,
+            max_reading: 1_000_000,
+            anomalies: vec![],
+            random_anomalies: 0,
+        }
+    }
+
+    /// Generates the series.
+    /// ### Panics
+    /// If `period` is below 3, which leaves no room for a zero and another reading in every window, or `max_reading`
+    /// is below 2 or above [`Generator::MAX_READING`]. If a chosen anomaly is within the first `period` readings,
+    /// which are never checked, or past the end, or no more than `period` readings from another.
+    pub fn generate(&self) -> Synthetic {
+        let period = self.period;
+        assert!(period >= 3, "Generated series need a period of at least 3.");
+        assert!(
+            (2..=Self::MAX_READING).contains(&self.max_reading),
+            "Generated series need a max_reading from 2 to {}.",
+            Self::MAX_READING
+        );
+        let mut random = SplitMix(self.seed);
+        let plan = self.plan(&mut random);
+        //Readings before a TooSmall, which must all be positive.
+        let mut zone = vec![false; self.len];
+        for (index, injection) in plan.iter().enumerate() {
+            if *injection == Some(Injection::TooSmall) {
+                zone[index - period..index].fill(true);
+            }
+        }
+        //Zeros that keep the readings before a TooSmall within max_reading: one right before them, and one every period
+        // before that, back to the last TooSmall. An anomaly in the way moves the first a reading earlier and the others
+        // a reading later, and none goes right after a TooSmall, where the reading before it may have doubled.
+        let mut lead = vec![false; self.len];
+        let after_too_small = |at: usize| at > 0 && plan[at - 1] == Some(Injection::TooSmall);
+        for (index, injection) in plan.iter().enumerate() {
+            if *injection != Some(Injection::TooSmall) || index == period {
+                continue;
+            }
+            let mut at = match plan[index - period - 1] {
+                None if !after_too_small(index - period - 1) => index - period - 1,
+                None | Some(Injection::TooSmall) => continue,
+                Some(_) => index - period - 2,
+            };
+            loop {
+                lead[at] = true;
+                let Some(before) = at.checked_sub(period) else {
+                    break;
+                };
+                at = match plan[before] {
+                    None if !after_too_small(before) => before,
+                    Some(Injection::TooSmall) => break,
+                    _ => before + 1,
+                };
+                if zone[at] || lead[at] {
+                    break;
+                }
+            }
+        }
+
+        let mut readings: Vec<u128> = Vec::with_capacity(self.len);
+        let mut anomalies = vec![];
+        let mut last_zero: Option<usize> = None;
+        for c in 0..self.len {
+            let window = &readings[c.saturating_sub(period)..];
+            let reading = match plan[c] {
+                Some(injection) => {
+                    let (reading, error) = inject(injection, c, window, &mut random);
+                    anomalies.push(error);
+                    reading
+                }
+                None if lead[c] || (!zone[c] && zero_due(c, last_zero, period, &plan)) => 0,
+                None if c < period => 2 * (1 + random.below(self.max_reading / 2)),
+                None => self.valid(window, &mut random),
+            };
+            if reading == 0 {
+                last_zero = Some(c);
+            }
+            readings.push(reading);
+        }
+        Synthetic {
+            readings,
+            anomalies,
+        }
+    }
+
+    ///The injection at each index, chosen then random.
+    fn plan(&self, random: &mut SplitMix) -> Vec<Option<Injection>> {
+        let mut plan = vec![None; self.len];
+        let mut taken: Vec<usize> = vec![];
+        let apart = |taken: &[usize], index: usize| {
+            taken
+                .iter()
+                .all(|&other| other.abs_diff(index) > self.period)
+        };
+        for &(index, injection) in &self.anomalies {
+            assert!(
+                (self.period..self.len).contains(&index),
+                "Anomaly at {} is not a checked reading.",
+                index
+            );
+            assert!(
+                apart(&taken, index),
+                "Anomaly at {} is within {} readings of another.",
+                index,
+                self.period
+            );
+            plan[index] = Some(injection);
+            taken.push(index);
+        }
+        if self.len <= self.period {
+            return plan;
+        }
+        const KINDS: [Injection; 3] = [Injection::TooLarge, Injection::TooSmall, Injection::NoPair];
+        for _ in 0..self.random_anomalies {
+            //A few tries each, then the series is full.
+            for _ in 0..64 {
+                let index = self.period + random.below((self.len - self.period) as u128) as usize;
+                if apart(&taken, index) {
+                    plan[index] = Some(KINDS[random.below(3) as usize]);
+                    taken.push(index);
+                    break;
+                }
+            }
+        }
+        plan
+    }
+
+    ///A positive reading that passes the collapse check against `window`.
+    fn valid(&self, window: &[u128], random: &mut SplitMix) -> u128 {
+        let even: Vec<u128> = window
+            .iter()
+            .copied()
+            .filter(|reading| *reading > 0 && reading % 2 == 0)
+            .collect();
+        if !window.contains(&0) {
+            //Only the last reading before a TooSmall that follows closely on another anomaly. It has to reach twice
+            // the smallest reading, which is itself the pair that climbs the least.
+            return 2 * window.iter().copied().min().unwrap();
+        }
+        let small: Vec<u128> = even
+            .iter()
+            .copied()
+            .filter(|reading| *reading <= self.max_reading)
+            .collect();
+        if small.is_empty() {
+            //Only spikes, or odd readings left in the window.
+            return match even.first() {
+                Some(&reading) => reading,
+                None => 2 * window.iter().copied().max().unwrap(),
+            };
+        }
+        let first = random.below(small.len() as u128) as usize;
+        let a = small[first];
+        //`a` itself, with the zero.
+        let mut candidates = vec![a];
+        if small.len() > 1 {
+            let second = (first + 1 + random.below(small.len() as u128 - 1) as usize) % small.len();
+            let b = small[second];
+            candidates.extend(a.checked_add(b).filter(|sum| *sum <= self.max_reading));
+            candidates.extend(Some(a.abs_diff(b)).filter(|difference| *difference > 0));
+        }
+        candidates[random.below(candidates.len() as u128) as usize]
+    }
+}
+
+///`true` when the last zero is about to leave the window, and the reading at `c` is the last chance to keep one.
+fn zero_due(c: usize, last_zero: Option<usize>, period: usize, plan: &[Option<Injection>]) -> bool {
+    let last_zero = match last_zero {
+        Some(last_zero) => last_zero,
+        None => return c == 0,
+    };
+    //An anomaly other than a zero takes the place of the zero, so it comes one reading early.
+    let displaced = matches!(
+        plan.get(c + 1),
+        Some(Some(Injection::TooLarge | Injection::NoPair))
+    );
+    c >= last_zero + period || (displaced && c + 1 >= last_zero + period)
+}
+
+///The reading for `injection` against `window`, and the error it causes.
+fn inject(
+    injection: Injection,
+    index: usize,
+    window: &[u128],
+    random: &mut SplitMix,
+) -> (u128, CollapseError) {
+    let min = window.iter().copied().min().unwrap();
+    let max = window.iter().copied().max().unwrap();
+    match injection {
+        Injection::TooLarge => {
+            let target = max
+                .checked_mul(2)
+                .and_then(|double| double.checked_add(2 + 2 * random.below(max / 2 + 1)))
+                .expect("Readings leave no room for a spike.");
+            let error = CollapseError::MaxTooSmall {
+                index,
+                target,
+                min,
+                max,
+            };
+            (target, error)
+        }
+        Injection::TooSmall => {
+            let error = CollapseError::MinTooBig {
+                index,
+                target: 0,
+                min,
+                max,
+            };
+            (0, error)
+        }
+        Injection::NoPair => {
+            //Odd, within [2 * min, 2 * max].
+            let target = 2 * min + 1 + 2 * random.below(max - min);
+            let error = CollapseError::NoPairFound {
+                index,
+                target,
+                min,
+                max,
+            };
+            (target, error)
+        }
+    }
+}
+
+impl Synthetic {
+    /// Writes the readings one per line, as `parse_readings` reads them.
+    pub fn write_readings(&self, out: &mut impl Write) -> io::Result<()> {
+        for reading in &self.readings {
+            writeln!(out, "{}", reading)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the ground truth as CSV, in the layout of the command line tool's `--all --format csv` report.
+    pub fn write_anomalies(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "index,kind,target,min,max")?;
+        for anomaly in &self.anomalies {
+            let (min, max) = anomaly.window().unwrap();
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                anomaly.index(),
+                anomaly.kind(),
+                anomaly.target(),
+                min,
+                max
+            )?;
+        }
+        Ok(())
+    }
+}
+
+///SplitMix64. Small, and the same sequence on every platform.
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    ///Near uniform in `0..bound`, which is plenty for test data.
+    fn below(&mut self, bound: u128) -> u128 {
+        ((u128::from(self.next()) << 64) | u128::from(self.next())) % bound
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use crate::reading::inverses;
+use crate::window_rule::early_warnings;
+use crate::{collapse_check_with, CollapseError, CollapseStrategy, Reading};
+
+/// Checks for imminent mine collapse of mining operation, without sorting.
+///
+/// Keeps the window as a multiset of reading counts, so checking whether the inverse of an element is in the
+/// window is O(1), and each reading costs O(period). Reaches the same verdicts as `collapse_check`.
+/// ### Arguments
+/// * `vector:` A vector
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::collapse_check_hashed;
+/// let vector = vec![1,2,3,4];
+/// assert_eq!(true, collapse_check_hashed(&vector, 3).is_ok());
+///
+/// let vector = vec![1,1,1,4];
+/// assert_eq!(false, collapse_check_hashed(&vector, 3).is_ok());
+/// ```
+pub fn collapse_check_hashed<T: Reading>(
+    vector: &[T],
+    period: usize,
+) -> Result<(), CollapseError<T>> {
+    collapse_check_with::<HashedWindow<T>>(vector, period)
+}
+
+/// The strategy behind `collapse_check_hashed`. Keeps the window as a multiset of reading counts.
+#[derive(Debug, Clone)]
+pub struct HashedWindow<T = u128> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Reading> CollapseStrategy for HashedWindow<T> {
+    type Reading = T;
+
+    fn from_preamble(preamble: &[T]) -> Self {
+        let mut counts: HashMap<T, usize> = HashMap::with_capacity(preamble.len());
+        for reading in preamble {
+            *counts.entry(*reading).or_insert(0) += 1;
+        } //O(n)
+        HashedWindow { counts }
+    }
+
+    fn check(&mut self, index: usize, target: T) -> Result<(), CollapseError<T>> {
+        pair_exists_hashed(&self.counts, index, target)
+    }
+
+    fn slide(&mut self, expired: T, incoming: T) {
+        match self.counts.get_mut(&expired) {
+            Some(count) if *count > 1 => *count -= 1,
+            _ => {
+                self.counts.remove(&expired);
+            }
+        }
+        *self.counts.entry(incoming).or_insert(0) += 1;
+    }
+}
+
+///Same three cases as `pair_exists`, on a window kept as reading counts. O(n).
+fn pair_exists_hashed<T: Reading>(
+    counts: &HashMap<T, usize>,
+    index: usize,
+    target: T,
+) -> Result<(), CollapseError<T>> {
+    let min = *counts.keys().min().unwrap();
+    let max = *counts.keys().max().unwrap();
+    //Early warnings
+    early_warnings(index, target, min, max)?;
+    //Normal warning. Is c-a in the values?
+    for element in counts.keys() {
+        if inverses(target, *element).any(|inverse| counts.contains_key(&inverse)) {
+            return Ok(());
+        }
+    } //O(n) -> On average, a non-faulty mine will hit this case.
+    Err(CollapseError::NoPairFound {
+        index,
+        target,
+        min,
+        max,
+    })
+}
@@ -0,0 +1,129 @@
+use std::cmp::Ordering;
+
+use crate::window_rule::early_warnings;
+use crate::{collapse_check_with, CollapseError, CollapseStrategy, Reading};
+
+/// Checks for imminent mine collapse of mining operation, keeping the window sorted as it slides.
+///
+/// Instead of re-sorting every step, the expiring reading is removed and the new one inserted at its sorted
+/// position by binary search, then the pair is found with two pointers. Each reading costs O(period) and
+/// never depends on how the sort handles nearly sorted input. Reaches the same verdicts as `collapse_check`.
+/// ### Arguments
+/// * `vector:` A vector
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::collapse_check_incremental;
+/// let vector = vec![1,2,3,4];
+/// assert_eq!(true, collapse_check_incremental(&vector, 3).is_ok());
+///
+/// let vector = vec![1,1,1,4];
+/// assert_eq!(false, collapse_check_incremental(&vector, 3).is_ok());
+/// ```
+pub fn collapse_check_incremental<T: Reading>(
+    vector: &[T],
+    period: usize,
+) -> Result<(), CollapseError<T>> {
+    collapse_check_with::<IncrementalWindow<T>>(vector, period)
+}
+
+/// The strategy behind `collapse_check_incremental`. Keeps the bare readings of the window sorted as it slides.
+#[derive(Debug, Clone)]
+pub struct IncrementalWindow<T = u128> {
+    pub(crate) sorted: Vec<T>,
+}
+
+impl<T: Reading> CollapseStrategy for IncrementalWindow<T> {
+    type Reading = T;
+
+    fn from_preamble(preamble: &[T]) -> Self {
+        let mut sorted = preamble.to_vec();
+        sorted.sort_unstable(); //O(n*log(n)) once.
+        IncrementalWindow { sorted }
+    }
+
+    fn check(&mut self, index: usize, target: T) -> Result<(), CollapseError<T>> {
+        pair_exists_two_pointer(&self.sorted, index, target)
+    }
+
+    //O(n) for the shift, O(log(n)) for the search.
+    fn slide(&mut self, expired: T, incoming: T) {
+        let position = self.sorted.partition_point(|entry| *entry < expired);
+        self.sorted.remove(position);
+        let position = self.sorted.partition_point(|entry| *entry < incoming);
+        self.sorted.insert(position, incoming);
+    }
+}
+
+///Same three cases as `pair_exists`, on a window of bare readings kept sorted.
+/// `pair_exists` accepts `target` when `|target - a|` is in the window, which is a sum `a + b = target`
+/// or a difference `a - b = target`, so both are searched with two pointers. O(n).
+pub(crate) fn pair_exists_two_pointer<T: Reading>(
+    sorted: &[T],
+    index: usize,
+    target: T,
+) -> Result<(), CollapseError<T>> {
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    //Early warnings
+    early_warnings(index, target, min, max)?;
+    match sum_exists(sorted, target) || difference_exists(sorted, target) {
+        true => Ok(()),
+        false => Err(CollapseError::NoPairFound {
+            index,
+            target,
+            min,
+            max,
+        }),
+    }
+}
+
+///`sorted[i] + sorted[j] == target` for some `i <= j`. The same slot may be used twice.
+fn sum_exists<T: Reading>(sorted: &[T], target: T) -> bool {
+    let (mut low, mut high) = (0, sorted.len() - 1);
+    while low <= high {
+        match sum_cmp(sorted[low], sorted[high], target) {
+            Ordering::Equal => return true,
+            Ordering::Less => low += 1,
+            Ordering::Greater if high == 0 => break,
+            Ordering::Greater => high -= 1,
+        }
+    }
+    false
+}
+
+///`low + high` against `target`, for `low <= high`. A sum past the range of the type is past any target,
+/// on the side of its sign, and both readings are negative when it falls below.
+pub(crate) fn sum_cmp<T: Reading>(low: T, high: T, target: T) -> Ordering {
+    match low.checked_add(high) {
+        Some(sum) => sum.cmp(&target),
+        None if low < T::ZERO => Ordering::Less,
+        None => Ordering::Greater,
+    }
+}
+
+///`a - b == target` for some `a` and `b` in the window. For a target below zero `a` is the smaller reading,
+/// so the gap between `sorted[i]` and `sorted[j]`, `i <= j`, is compared with the target from the other side.
+fn difference_exists<T: Reading>(sorted: &[T], target: T) -> bool {
+    let (mut low, mut high) = (0, 0);
+    while high < sorted.len() {
+        //How the gap compares with the one the target needs. A gap past the range of the type is too wide.
+        let gap = match target >= T::ZERO {
+            true => sorted[high]
+                .checked_sub(sorted[low])
+                .map_or(Ordering::Greater, |gap| gap.cmp(&target)),
+            false => sorted[low]
+                .checked_sub(sorted[high])
+                .map_or(Ordering::Greater, |gap| target.cmp(&gap)),
+        };
+        match gap {
+            Ordering::Equal => return true,
+            Ordering::Less => high += 1,
+            Ordering::Greater => {
+                low += 1;
+                high = high.max(low);
+            }
+        }
+    }
+    false
+}
@@ -0,0 +1,222 @@
+use std::cmp::Ordering;
+
+use crate::reading::sorted_sum_cmp;
+use crate::{
+    collapse_check_with, collapse_scan_with, CollapseError, CollapseStrategy, IncrementalWindow,
+    Reading,
+};
+
+/// The sum model with the number of readings as a parameter. A reading is safe when it is the sum of `k` readings
+/// from the window, so `k = 1` asks for a repeat of a window reading and `k = 3` for a sum of three.
+///
+/// Unlike [`PairRule::Legacy`](crate::PairRule::Legacy), only sums count, never differences.
+/// With `distinct`, the `k` readings come from `k` different positions in the window, otherwise one reading can be
+/// used more than once. `KSum { k: 2, distinct: true }` is [`PairRule::Strict`](crate::PairRule::Strict) without
+/// `distinct_values`.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::KSum;
+/// //9 = 2 + 3 + 4, but no two readings in the window sum to it.
+/// let vector = vec![2, 3, 4, 9];
+/// assert_eq!(true, KSum { k: 3, distinct: true }.collapse_check(&vector, 3).is_ok());
+/// assert_eq!(false, KSum { k: 2, distinct: true }.collapse_check(&vector, 3).is_ok());
+///
+/// //12 = 4 + 4 + 4, one reading used three times.
+/// let vector = vec![2, 3, 4, 12];
+/// assert_eq!(true, KSum { k: 3, distinct: false }.collapse_check(&vector, 3).is_ok());
+/// assert_eq!(false, KSum { k: 3, distinct: true }.collapse_check(&vector, 3).is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KSum {
+    /// How many window readings sum to each reading. 1, 2 or 3, larger models can use [`KSumWindow`] directly.
+    pub k: usize,
+    /// Whether the `k` readings must come from different positions in the window.
+    pub distinct: bool,
+}
+
+impl KSum {
+    /// Runs the collapse check under this model.
+    /// ### Arguments
+    /// * `vector:` A vector
+    /// * `period:` Determines the range `i..i+k`, where i+k is not included.
+    /// ### Panics
+    /// If `k` is not 1, 2 or 3.
+    pub fn collapse_check<T: Reading>(
+        self,
+        vector: &[T],
+        period: usize,
+    ) -> Result<(), CollapseError<T>> {
+        match (self.k, self.distinct) {
+            (1, _) => collapse_check_with::<KSumWindow<1, false, T>>(vector, period),
+            (2, false) => collapse_check_with::<KSumWindow<2, false, T>>(vector, period),
+            (2, true) => collapse_check_with::<KSumWindow<2, true, T>>(vector, period),
+            (3, false) => collapse_check_with::<KSumWindow<3, false, T>>(vector, period),
+            (3, true) => collapse_check_with::<KSumWindow<3, true, T>>(vector, period),
+            (k, _) => panic!("KSum checks sums of 1, 2 or 3 readings, not {}.", k),
+        }
+    }
+
+    /// Runs `collapse_scan` under this model.
+    /// ### Panics
+    /// If `k` is not 1, 2 or 3.
+    pub fn collapse_scan<T: Reading>(self, vector: &[T], period: usize) -> Vec<CollapseError<T>> {
+        match (self.k, self.distinct) {
+            (1, _) => collapse_scan_with::<KSumWindow<1, false, T>>(vector, period),
+            (2, false) => collapse_scan_with::<KSumWindow<2, false, T>>(vector, period),
+            (2, true) => collapse_scan_with::<KSumWindow<2, true, T>>(vector, period),
+            (3, false) => collapse_scan_with::<KSumWindow<3, false, T>>(vector, period),
+            (3, true) => collapse_scan_with::<KSumWindow<3, true, T>>(vector, period),
+            (k, _) => panic!("KSum checks sums of 1, 2 or 3 readings, not {}.", k),
+        }
+    }
+}
+
+/// The strategy behind [`KSum`], for any `K` of at least 1. Keeps the window sorted as it slides, like
+/// [`IncrementalWindow`], fixes the smallest `K - 2` readings of each sum in turn and finds the last two with
+/// two pointers. Each reading costs O(period) for `K` up to 2, and O(period^(K-1)) after that.
+#[derive(Debug, Clone)]
+pub struct KSumWindow<const K: usize, const DISTINCT: bool, T = u128>(IncrementalWindow<T>);
+
+impl<const K: usize, const DISTINCT: bool, T: Reading> CollapseStrategy
+    for KSumWindow<K, DISTINCT, T>
+{
+    type Reading = T;
+
+    fn from_preamble(preamble: &[T]) -> Self {
+        assert!(K > 0, "A sum of zero readings reaches nothing.");
+        KSumWindow(IncrementalWindow::from_preamble(preamble))
+    }
+
+    fn check(&mut self, index: usize, target: T) -> Result<(), CollapseError<T>> {
+        ksum_check(&self.0.sorted, K, DISTINCT, index, target)
+    }
+
+    fn slide(&mut self, expired: T, incoming: T) {
+        self.0.slide(expired, incoming);
+    }
+}
+
+///Same three cases as `pair_exists`, for sums of `k` readings of a window kept sorted.
+pub(crate) fn ksum_check<T: Reading>(
+    sorted: &[T],
+    k: usize,
+    distinct: bool,
+    index: usize,
+    target: T,
+) -> Result<(), CollapseError<T>> {
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    let mut terms = Vec::with_capacity(k);
+    match target {
+        //Early warnings. A sum of k readings can never leave [k * min, k * max].
+        target if bound_cmp(&mut terms, max, max, k, target) == Ordering::Less => {
+            Err(CollapseError::MaxTooSmall {
+                index,
+                target,
+                min,
+                max,
+            })
+        }
+        target if bound_cmp(&mut terms, min, min, k, target) == Ordering::Greater => {
+            Err(CollapseError::MinTooBig {
+                index,
+                target,
+                min,
+                max,
+            })
+        }
+        _ if sum_of_k_exists(sorted, k, target, distinct, &mut terms) => Ok(()),
+        _ => Err(CollapseError::NoPairFound {
+            index,
+            target,
+            min,
+            max,
+        }),
+    }
+}
+
+///`k` readings of `sorted`, at ascending positions, that sum to `target`. Positions may repeat unless `distinct`.
+fn sum_of_k_exists<T: Reading>(
+    sorted: &[T],
+    k: usize,
+    target: T,
+    distinct: bool,
+    terms: &mut Vec<T>,
+) -> bool {
+    match k {
+        1 => sorted.binary_search(&target).is_ok(),
+        k => fixed_sum_exists(sorted, 0, k, target, distinct, terms),
+    }
+}
+
+///Fixes one more term from `sorted[start..]` until two are left, then finds them with two pointers.
+/// `terms` holds the terms fixed so far, in ascending order.
+fn fixed_sum_exists<T: Reading>(
+    sorted: &[T],
+    start: usize,
+    k: usize,
+    target: T,
+    distinct: bool,
+    terms: &mut Vec<T>,
+) -> bool {
+    if start >= sorted.len() {
+        return false;
+    }
+    if k == 2 {
+        return two_pointer_exists(sorted, start, target, distinct, terms);
+    }
+    for i in start..sorted.len() {
+        //An equal term was already fixed, with at least as many readings left after it.
+        if i > start && sorted[i] == sorted[i - 1] {
+            continue;
+        }
+        //Every term left is at least sorted[i], and at most the window maximum.
+        if bound_cmp(terms, sorted[i], sorted[i], k, target) == Ordering::Greater {
+            break;
+        }
+        if bound_cmp(terms, sorted[i], sorted[sorted.len() - 1], k, target) == Ordering::Less {
+            continue;
+        }
+        terms.push(sorted[i]);
+        let next = if distinct { i + 1 } else { i };
+        let found = fixed_sum_exists(sorted, next, k - 1, target, distinct, terms);
+        terms.pop();
+        if found {
+            return true;
+        }
+    } //O(n^(k-2)) calls to the two pointers.
+    false
+}
+
+///`terms + sorted[low] + sorted[high] == target` for some `start <= low <= high`, `low < high` if `distinct`. O(n).
+fn two_pointer_exists<T: Reading>(
+    sorted: &[T],
+    start: usize,
+    target: T,
+    distinct: bool,
+    terms: &mut Vec<T>,
+) -> bool {
+    let (mut low, mut high) = (start, sorted.len() - 1);
+    while low < high || (!distinct && low == high) {
+        terms.extend([sorted[low], sorted[high]]);
+        let ordering = sorted_sum_cmp(terms, target);
+        terms.truncate(terms.len() - 2);
+        match ordering {
+            Ordering::Equal => return true,
+            Ordering::Less => low += 1,
+            Ordering::Greater if high == 0 => break,
+            Ordering::Greater => high -= 1,
+        }
+    }
+    false
+}
+
+///`terms + first + rest * (k - 1)` against `target`, for `terms <= first <= rest`.
+fn bound_cmp<T: Reading>(terms: &mut Vec<T>, first: T, rest: T, k: usize, target: T) -> Ordering {
+    let fixed = terms.len();
+    terms.push(first);
+    terms.extend(std::iter::repeat_n(rest, k - 1));
+    let ordering = sorted_sum_cmp(terms, target);
+    terms.truncate(fixed);
+    ordering
+}
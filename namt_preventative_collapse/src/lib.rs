@@ -3,8 +3,121 @@
 //! namt_preventative_collapse is an early warning system for mining operations.
 //! Best implemented in all hardware that comes into contact with rocks, or receives mining data.
 //! For most applications `collapse_check` will suffice, but the underlying search function `pairs_exist` is
-//! exported for convenience.
-use std::vec;
+//! exported for convenience. Post-incident reviews should use `collapse_scan`, which reports every anomaly in a series,
+//! and audits can use `collapse_witnesses`, which reports the pair that validated each reading.
+//! Hardware that receives readings one at a time should use `CollapseMonitor`, and a collector that receives them from
+//! every sensor of a mine should use `MineMonitor`, which keeps a window per sensor and aggregates a [`MineStatus`].
+//! A monitor saves its state as a [`Checkpoint`], so a collector that restarts resumes without missing a reading.
+//! For long periods, `collapse_check_hashed` and `collapse_check_incremental` avoid sorting the window and scale
+//! linearly with `period`. Archived logs are re-audited on every core with `collapse_check_parallel` and
+//! `collapse_scan_parallel`, with the `rayon` feature. Every implementation, including the `bench_iterations`
+//! experiments, is a [`CollapseStrategy`]: `collapse_check_with` selects one at compile time, and [`Strategy`] at run
+//! time.
+//!
+//! By default a reading is safe when `|target - a|` is in the window, which also accepts one reading used twice and
+//! differences. [`PairRule::Strict`] opts into genuine sums of two different readings only, and [`KSum`] into sums
+//! of one, two or three readings.
+//!
+//! Every check reports failures as a [`CollapseError`], which tells the early warnings apart from the normal warning.
+//! Mine profiles that need other conditions compose [`WindowRule`]s, such as the early warnings [`MaxBound`] and
+//! [`MinBound`], [`WithinRange`], [`WithinDeviations`] or a custom [`FnRule`], and run them with `collapse_check_rule`.
+//!
+//! Readings can be any primitive integer type, signed or unsigned, through the [`Reading`] trait, so sensor buffers
+//! are checked as they are, without widening them to `u128`. Calibrated `f64` readings with measurement noise use
+//! `collapse_check_tolerant`, which accepts pairs within a [`Tolerance`] of each reading.
+//!
+//! Logged readings are read with `parse_readings`, which skips blank lines and `#` comments and reports a malformed
+//! line as a [`ParseError`] with its line and column, or with `parse_readings_lenient`, which skips it instead.
+//! Every check borrows its readings as a slice. Sources that are not one, such as ring buffers or parsers, are checked
+//! as they are read with `collapse_check_iter` and `collapse_scan_iter`, and `parse_readings_iter` parses a log one
+//! reading at a time. With the `mmap` feature, [`MappedReadings`] maps a log file into memory, so multi-gigabyte logs
+//! are checked without copying them.
+//! Logger CSV output is read with `parse_csv` into timestamped [`Record`]s, and `collapse_check_records` checks each
+//! sensor as its own series and reports a [`Violation`] with the timestamp and sensor of the failing reading.
+//! Loggers that sample at irregular intervals can use a window defined by duration instead of `period`, with
+//! `collapse_check_timed`, [`TimedMonitor`] or `collapse_check_records_timed`.
+//!
+//! Operators who need graded alerts rather than a verdict per reading feed the verdicts to an [`AlertTracker`], which
+//! raises and clears a [`Severity`] under an [`AlertPolicy`] with hysteresis, or grade a whole series with
+//! `collapse_alerts`.
+//!
+//! Tools that read results rather than people use a [`Report`], which sums up the input, how it was checked and
+//! every anomaly. With the `serde` feature, reports and every result type are `Serialize`.
+//!
+//! Tests and benchmarks that need more than the bundled inputs use a [`Generator`], which produces seeded series for
+//! any period and length, with anomalies injected at chosen or random indexes and the ground truth to check them by.
+//!
+//! The library never prints. Hosts that want to follow a check as it runs pass a [`CollapseObserver`] to
+//! `collapse_check_observed`, `collapse_scan_observed` or [`CollapseMonitor::push_observed`], and route its events
+//! wherever they want, or to the `log` or `tracing` crates with the features of the same name.
+mod alert;
+mod csv;
+mod error;
+mod generate;
+mod hashed;
+mod incremental;
+mod ksum;
+mod mine;
+mod monitor;
+mod observer;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod parse_readings;
+mod reading;
+mod report;
+mod rule;
+mod source;
+mod strategy;
+mod timed;
+mod tolerance;
+mod window_rule;
+mod witness;
+
+pub use alert::{collapse_alerts, Alert, AlertPolicy, AlertTracker, Severity};
+pub use csv::{
+    collapse_check_records, collapse_check_records_timed, collapse_scan_records,
+    collapse_scan_records_timed, parse_csv, Column, CsvFormat, Header, Record, Violation,
+};
+pub use error::{CollapseError, ParseError, ParseErrorKind};
+pub use generate::{Generator, Injection, Synthetic};
+pub use hashed::{collapse_check_hashed, HashedWindow};
+pub use incremental::{collapse_check_incremental, IncrementalWindow};
+pub use ksum::{KSum, KSumWindow};
+pub use mine::{MineMonitor, MineStatus};
+pub use monitor::{Checkpoint, CollapseMonitor, Verdict};
+#[cfg(feature = "log")]
+pub use observer::LogObserver;
+#[cfg(feature = "tracing")]
+pub use observer::TracingObserver;
+pub use observer::{collapse_check_observed, collapse_scan_observed, CollapseObserver};
+#[cfg(feature = "rayon")]
+pub use parallel::{
+    collapse_check_parallel, collapse_check_parallel_with, collapse_scan_parallel,
+    collapse_scan_parallel_with,
+};
+pub use parse_readings::{
+    parse_readings, parse_readings_iter, parse_readings_lenient, LenientReadings,
+};
+pub use reading::Reading;
+pub use report::{Report, Summary};
+pub use rule::{PairRule, StrictWindow};
+#[cfg(feature = "mmap")]
+pub use source::MappedReadings;
+pub use source::{collapse_check_iter, collapse_scan_iter};
+pub use strategy::{
+    collapse_check_with, collapse_scan_with, CollapseStrategy, ResortWindow, Strategy,
+};
+pub use timed::{collapse_check_timed, collapse_scan_timed, parse_timestamp, TimedMonitor};
+pub use tolerance::{collapse_check_tolerant, pair_exists_tolerant, Tolerance};
+pub use window_rule::{
+    collapse_check_rule, collapse_scan_rule, FnRule, MaxBound, MinBound, WindowRule,
+    WithinDeviations, WithinRange,
+};
+pub use witness::{collapse_witnesses, pair_witness, write_witness_report, Witness};
+
+use reading::inverses;
+use window_rule::early_warnings;
+
 /// Checks for imminent mine collapse of mining operation.
 /// ### Arguments
 /// * `vector:` A vector
@@ -12,159 +125,158 @@ use std::vec;
 /// ### Example
 /// ```
 /// use namt_preventative_collapse::collapse_check;
-/// let mut vector = vec![1,2,3,4];
-/// let result = collapse_check(&mut vector, 3);
+/// let vector = vec![1,2,3,4];
+/// let result = collapse_check(&vector, 3);
 /// assert_eq!(true, result.is_ok());
 ///
-/// let mut vector = vec![1,1,1,4];
+/// let vector = vec![1,1,1,4];
 /// let period = 3;
-/// let result = collapse_check(&mut vector, 3);
+/// let result = collapse_check(&vector, 3);
 /// assert_eq!(false, result.is_ok());
+///
+/// //Signed readings reach negative targets too, -3 + 1 = -2.
+/// let vector: Vec<i64> = vec![-3, 1, 4, -2];
+/// assert_eq!(true, collapse_check(&vector, 3).is_ok());
 /// ```
-pub fn collapse_check(vector: &mut Vec<u128>, period: usize) -> Result<(), String> {
-    if vector.len() <= period {
-        return Ok(()); //Safe.
-    }
-    let mut values: Vec<(u128, usize)> = vec![]; //O(n)
-    for x in 0..period {
-        values.push((vector[x], x.try_into().unwrap()));
-    }
-
-    for c in period..vector.len() {
-        values.sort_by(|(entry_a, _), (entry_b, _)| entry_a.cmp(entry_b)); //O(n*log(n)) first time. O(n) every time after.
-
-        '_ensure_sorting: for x in 0..period - 1 {
-            assert!(values[x].0 <= values[x + 1].0);
-            // Scaffolding. Do not remove. Any logic after this assertion block relies on the assumption the values are sorted.
-            // Also avoiding nightly build of Rust, .is_sorted();
-        } // O(n)
-
-        let target = vector[c];
-        let result = pair_exists(&values, target);
-        if result.is_err() {
-            eprintln!("Index: {}, Element: {} ", c, vector[c]);
-            return result;
-        }
+pub fn collapse_check<T: Reading>(vector: &[T], period: usize) -> Result<(), CollapseError<T>> {
+    collapse_check_with::<ResortWindow<T>>(vector, period)
+}
 
-        //Decrement indexes, and insert (vector[c], period -1) in the place of (some_element, 0).
-        for x in 0..values.len() {
-            if values[x].1 != 0 {
-                values[x].1 -= 1;
-                continue;
-            }
-            values[x] = (target.clone(), period - 1);
-        } //O(n)
-          //remove zero index
-        for x in 0..values.len() {
-            assert!(values[x].1 < period, "Logic Error: Indexing operations.");
-        }
-    }
-    Ok(())
+/// Walks the whole series and reports every reading that fails the collapse check, instead of stopping at the first.
+/// Failing readings still enter the window, so every index is judged against exactly the `period` readings before it.
+/// ### Arguments
+/// * `vector:` A vector
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_scan, CollapseError};
+/// let vector = vec![1, 1, 1, 4, 2, 9];
+/// let anomalies = collapse_scan(&vector, 3);
+/// assert_eq!(
+///     anomalies.iter().map(CollapseError::index).collect::<Vec<_>>(),
+///     vec![3, 5]
+/// );
+/// ```
+pub fn collapse_scan<T: Reading>(vector: &[T], period: usize) -> Vec<CollapseError<T>> {
+    collapse_scan_with::<ResortWindow<T>>(vector, period)
 }
 
-///     
+///
 /// Checks for existence of `a` and `b` in `values` such that `a + b = target`, and returns a Result.
 /// Uses binary_search which assumes `values` is sorted by the predicate `values[n] <= values[n+1]`, otherwise the result is meaningless.
+/// `index` is the position of `target` in the original input, and is only used to report errors.
 ///
-pub fn pair_exists(values: &Vec<(u128, usize)>, target: u128) -> Result<(), String> {
+pub fn pair_exists<T: Reading>(
+    values: &[(T, usize)],
+    index: usize,
+    target: T,
+) -> Result<(), CollapseError<T>> {
+    pair_position(values, index, target).map(|_| ())
+}
+
+///The search behind `pair_exists`. Keeps the positions in `values` of the pair that matched.
+pub(crate) fn pair_position<T: Reading>(
+    values: &[(T, usize)],
+    index: usize,
+    target: T,
+) -> Result<(usize, usize), CollapseError<T>> {
     let min = values[0].0;
     let max = values[values.len() - 1].0;
-    match target {
-        //Early warnings
-        //Match is used for maintainance clarity of cases.
-        target if 2 * max < target => {
-            Err("Imminent Failure: Maximum is too small to reach the required sum.".to_string())
-        }
-        target if 2 * min > target => {
-            Err("Imminent Failure: Minimum is too big to reach the required sum.".to_string())
-        }
-        _ => {
-            //Normal warning. Is c-a in the values?
-            for (element, _) in values.iter() {
-                let inverse = match target >= *element {
-                    true => target - element,
-                    false => element - target,
-                }; //Technically an absolute value would work here.
-
-                if values
-                    .binary_search_by(|tuple_ptr| tuple_ptr.0.cmp(&inverse))
-                    .is_ok()
-                {
-                    return Ok(());
-                }
-            } //O(n*log(n)) -> On average, a non-faulty mine will hit this case.
-            Err("Imminent Failure detected. No pairs reach the required sum.".to_string())
+    //Early warnings, the MaxBound and MinBound rules.
+    early_warnings(index, target, min, max)?;
+    //Normal warning. Is c-a in the values?
+    for (position, (element, _)) in values.iter().enumerate() {
+        for inverse in inverses(target, *element) {
+            if let Ok(inverse_position) =
+                values.binary_search_by(|tuple_ptr| tuple_ptr.0.cmp(&inverse))
+            {
+                return Ok((position, inverse_position));
+            }
         }
-    }
+    } //O(n*log(n)) -> On average, a non-faulty mine will hit this case.
+    Err(CollapseError::NoPairFound {
+        index,
+        target,
+        min,
+        max,
+    })
 }
 
 ///A module specifically for benching multiple iterations of the collapse_check function.
+///
+/// Every experiment except `collapse_check_are_references_better` is a [`CollapseStrategy`], so it shares the
+/// window bookkeeping with `collapse_check` and only differs in the pair search.
+/// The references experiment borrows the input instead of owning a window, so it keeps its own loop.
 pub mod bench_iterations {
-    use std::vec;
+    use super::reading::{double_is_above, double_is_below};
+    use super::strategy::PositionedWindow;
+    use super::{collapse_check_with, CollapseError, CollapseStrategy};
     ///Allocates on each iteration of the loop. Verifying a bad idea.
     pub fn collapse_check_alloc_and_sort_on_each_loop(
-        vector: &mut Vec<u128>,
+        vector: &[u128],
         period: usize,
-    ) -> Result<(), String> {
-        for c in period..vector.len() {
-            let mut values: Vec<(u128, usize)> = vec![]; //O(n)
-            for x in 0..c {
-                values.push((vector[x], x.try_into().unwrap())); //have to fix this clone.
-            }
-            values.sort_by(|(entry_a, _), (entry_b, _)| entry_a.cmp(entry_b)); //O(n*log(n)) first time. O(n) every time after.
+    ) -> Result<(), CollapseError> {
+        collapse_check_with::<AllocAndSortOnEachLoop>(vector, period)
+    }
 
-            '_ensure_sorting: for x in 0..period - 1 {
-                assert!(values[x].0 <= values[x + 1].0);
-                // Scaffolding. Do not remove. Any logic after this assertion block relies on the assumption the values are sorted.
-                // Also avoiding nightly build of Rust, .is_sorted();
-            } // O(n)
+    ///Strategy behind `collapse_check_alloc_and_sort_on_each_loop`. Checks each reading against every reading
+    /// before it, not just the window, copying and sorting all of them on every check.
+    #[derive(Debug, Clone)]
+    pub struct AllocAndSortOnEachLoop(Vec<u128>);
 
-            let target = vector[c];
-            let result = super::pair_exists(&values, target);
-            if result.is_err() {
-                return result;
-            }
+    impl CollapseStrategy for AllocAndSortOnEachLoop {
+        type Reading = u128;
+
+        fn from_preamble(preamble: &[u128]) -> Self {
+            AllocAndSortOnEachLoop(preamble.to_vec())
+        }
+
+        fn check(&mut self, index: usize, target: u128) -> Result<(), CollapseError> {
+            let mut values: Vec<(u128, usize)> = self.0.iter().copied().zip(0..).collect(); //O(n)
+            values.sort_by_key(|(entry, _)| *entry); //O(n*log(n)) every time.
+            super::pair_exists(&values, index, target)
+        }
+
+        fn slide(&mut self, _expired: u128, incoming: u128) {
+            //Nothing expires.
+            self.0.push(incoming);
         }
-        Ok(())
     }
 
     //No. It's slower on average.
     ///Does not allocate a local vector. Uses only references.
     pub fn collapse_check_are_references_better(
-        vector: &mut Vec<u128>,
+        vector: &[u128],
         period: usize,
-    ) -> Result<(), String> {
+    ) -> Result<(), CollapseError> {
         if vector.len() <= period {
             return Ok(()); //Safe.
         }
-        let (safe, r#unsafe) = vector.split_at_mut(period);
+        let (safe, r#unsafe) = vector.split_at(period);
         let mut safe: Vec<(&u128, usize)> = safe.iter().zip(0..period).collect(); //O(n)
 
-        for element in r#unsafe.iter() {
-            safe.sort_by(|(entry_a, _), (entry_b, _)| (entry_a).cmp(entry_b)); //O(n*log(n)) first time. O(n) every time after.
+        for (c, element) in (period..).zip(r#unsafe.iter()) {
+            safe.sort_by_key(|(entry, _)| *entry); //O(n*log(n)) first time. O(n) every time after.
 
-            '_ensure_sorting: for x in 0..period - 1 {
-                assert!(safe[x].0 <= safe[x + 1].0);
+            '_ensure_sorting: for pair in safe.windows(2) {
+                assert!(pair[0].0 <= pair[1].0);
                 // Scaffolding. Do not remove. Any logic after this assertion block relies on the assumption the values are sorted.
                 // Also avoiding nightly build of Rust, .is_sorted();
             } // O(n)
 
-            let result = pair_exists_references(&safe, *element);
-            if result.is_err() {
-                return result;
-            }
+            pair_exists_references(&safe, c, *element)?;
 
             //Decrement indexes, and insert (vector[c], period -1) in the place of (some_element, 0).
-            for x in 0..safe.len() {
-                if safe[x].1 != 0 {
-                    safe[x].1 -= 1;
+            for entry in safe.iter_mut() {
+                if entry.1 != 0 {
+                    entry.1 -= 1;
                     continue;
                 }
-                safe[x] = (element, period - 1);
+                *entry = (element, period - 1);
             } //O(n)
               //remove zero index
-            for x in 0..safe.len() {
-                assert!(safe[x].1 < period, "Logic Error: Indexing operations.");
+            for entry in safe.iter() {
+                assert!(entry.1 < period, "Logic Error: Indexing operations.");
             }
         }
         Ok(())
@@ -173,149 +285,114 @@ pub mod bench_iterations {
     //No
     ///Includes more short circuits to see if improving on the best case scenario is worthwhile.
     pub fn collapse_check_with_more_short_circuiting(
-        vector: &mut Vec<u128>,
+        vector: &[u128],
         period: usize,
-    ) -> Result<(), String> {
-        if vector.len() <= period {
-            return Ok(()); //Safe.
-        }
-        let (safe, r#unsafe) = vector.split_at_mut(period);
-        let mut safe: Vec<(&u128, usize)> = safe.iter().zip(0..period).collect(); //O(n)
+    ) -> Result<(), CollapseError> {
+        collapse_check_with::<MoreShortCircuiting>(vector, period)
+    }
 
-        for element in r#unsafe.iter() {
-            safe.sort_by(|(entry_a, _), (entry_b, _)| (entry_a).cmp(entry_b)); //O(n*log(n)) first time. O(n) every time after.
+    ///Strategy behind `collapse_check_with_more_short_circuiting`. Repeats the early warnings for every element.
+    #[derive(Debug, Clone)]
+    pub struct MoreShortCircuiting(PositionedWindow);
 
-            '_ensure_sorting: for x in 0..period - 1 {
-                assert!(safe[x].0 <= safe[x + 1].0);
-                // Scaffolding. Do not remove. Any logic after this assertion block relies on the assumption the values are sorted.
-                // Also avoiding nightly build of Rust, .is_sorted();
-            } // O(n)
+    impl CollapseStrategy for MoreShortCircuiting {
+        type Reading = u128;
 
-            let result = pair_exists_references_with_short_circuit(&safe, *element);
-            if result.is_err() {
-                return result;
-            }
+        fn from_preamble(preamble: &[u128]) -> Self {
+            MoreShortCircuiting(PositionedWindow::from_preamble(preamble))
+        }
 
-            //Decrement indexes, and insert (vector[c], period -1) in the place of (some_element, 0).
-            for x in 0..safe.len() {
-                if safe[x].1 != 0 {
-                    safe[x].1 -= 1;
-                    continue;
-                }
-                safe[x] = (element, period - 1);
-            } //O(n)
-              //remove zero index
-            for x in 0..safe.len() {
-                assert!(safe[x].1 < period, "Logic Error: Indexing operations.");
-            }
+        fn check(&mut self, index: usize, target: u128) -> Result<(), CollapseError> {
+            pair_exists_with_short_circuit(self.0.sorted(), index, target)
+        }
+
+        fn slide(&mut self, _expired: u128, incoming: u128) {
+            self.0.slide(incoming);
         }
-        Ok(())
     }
 
     //No, 'cause short circuiting is a best case scenario. Is basically about the same speed.
     ///Removes all short circuiting.
     pub fn collapse_check_no_short_ciruit(
-        vector: &mut Vec<u128>,
+        vector: &[u128],
         period: usize,
-    ) -> Result<(), String> {
-        if vector.len() <= period {
-            return Ok(()); //Safe.
-        }
-        let mut values: Vec<(u128, usize)> = vec![]; //O(n)
-        for x in 0..period {
-            values.push((vector[x], x.try_into().unwrap())); //have to fix this clone.
-        }
+    ) -> Result<(), CollapseError> {
+        collapse_check_with::<NoShortCircuit>(vector, period)
+    }
 
-        for c in period..vector.len() {
-            values.sort_by(|(entry_a, _), (entry_b, _)| entry_a.cmp(entry_b)); //O(n*log(n)) first time. O(n) every time after.
+    ///Strategy behind `collapse_check_no_short_ciruit`. Skips the early warnings entirely.
+    #[derive(Debug, Clone)]
+    pub struct NoShortCircuit(PositionedWindow);
 
-            '_ensure_sorting: for x in 0..period - 1 {
-                assert!(values[x].0 <= values[x + 1].0);
-                // Scaffolding. Do not remove. Any logic after this assertion block relies on the assumption the values are sorted.
-                // Also avoiding nightly build of Rust, .is_sorted();
-            } // O(n)
+    impl CollapseStrategy for NoShortCircuit {
+        type Reading = u128;
 
-            let target = vector[c];
-            let result = pair_exists_no_short_circuit(&values, target);
-            if result.is_err() {
-                return result;
-            }
+        fn from_preamble(preamble: &[u128]) -> Self {
+            NoShortCircuit(PositionedWindow::from_preamble(preamble))
+        }
 
-            //Decrement indexes, and insert (vector[c], period -1) in the place of (some_element, 0).
-            for x in 0..values.len() {
-                if values[x].1 != 0 {
-                    values[x].1 -= 1;
-                    continue;
-                }
-                values[x] = (target.clone(), period - 1);
-            } //O(n)
-              //remove zero index
-            for x in 0..values.len() {
-                assert!(values[x].1 < period, "Logic Error: Indexing operations.");
-            }
+        fn check(&mut self, index: usize, target: u128) -> Result<(), CollapseError> {
+            pair_exists_no_short_circuit(self.0.sorted(), index, target)
+        }
+
+        fn slide(&mut self, _expired: u128, incoming: u128) {
+            self.0.slide(incoming);
         }
-        Ok(())
     }
 
     //Also, no. Worth revisiting as it may reduce complexity as n grows.
     ///Takes advantage of the sorted list to narrow the search area of the binary search as it goes.
     /// Should improve on the worst case scenario.
     pub fn collapse_check_narrowing_search(
-        vector: &mut Vec<u128>,
+        vector: &[u128],
         period: usize,
-    ) -> Result<(), String> {
-        if vector.len() <= period {
-            return Ok(()); //Safe.
-        }
-        let mut values: Vec<(u128, usize)> = vec![]; //O(n)
-        for x in 0..period {
-            values.push((vector[x], x.try_into().unwrap())); //have to fix this clone.
-        }
+    ) -> Result<(), CollapseError> {
+        collapse_check_with::<NarrowingSearch>(vector, period)
+    }
 
-        for c in period..vector.len() {
-            values.sort_by(|(entry_a, _), (entry_b, _)| entry_a.cmp(entry_b)); //O(n*log(n)) first time. O(n) every time after.
+    ///Strategy behind `collapse_check_narrowing_search`.
+    #[derive(Debug, Clone)]
+    pub struct NarrowingSearch(PositionedWindow);
 
-            '_ensure_sorting: for x in 0..period - 1 {
-                assert!(values[x].0 <= values[x + 1].0);
-                // Scaffolding. Do not remove. Any logic after this assertion block relies on the assumption the values are sorted.
-                // Also avoiding nightly build of Rust, .is_sorted();
-            } // O(n)
+    impl CollapseStrategy for NarrowingSearch {
+        type Reading = u128;
 
-            let target = vector[c];
-            let result = pair_exists_narrowing(&values, target);
-            if result.is_err() {
-                return result;
-            }
+        fn from_preamble(preamble: &[u128]) -> Self {
+            NarrowingSearch(PositionedWindow::from_preamble(preamble))
+        }
 
-            //Decrement indexes, and insert (vector[c], period -1) in the place of (some_element, 0).
-            for x in 0..values.len() {
-                if values[x].1 != 0 {
-                    values[x].1 -= 1;
-                    continue;
-                }
-                values[x] = (target.clone(), period - 1);
-            } //O(n)
-              //remove zero index
-            for x in 0..values.len() {
-                assert!(values[x].1 < period, "Logic Error: Indexing operations.");
-            }
+        fn check(&mut self, index: usize, target: u128) -> Result<(), CollapseError> {
+            pair_exists_narrowing(self.0.sorted(), index, target)
+        }
+
+        fn slide(&mut self, _expired: u128, incoming: u128) {
+            self.0.slide(incoming);
         }
-        Ok(())
     }
 
     ///Used in collapse_check_are_references_better.
-    fn pair_exists_references(values: &Vec<(&u128, usize)>, target: u128) -> Result<(), String> {
-        let min = values[0].0;
-        let max = values[values.len() - 1].0;
+    fn pair_exists_references(
+        values: &[(&u128, usize)],
+        index: usize,
+        target: u128,
+    ) -> Result<(), CollapseError> {
+        let min = *values[0].0;
+        let max = *values[values.len() - 1].0;
         match target {
             //Early warnings
             //Match solely for maintainance clarity, there are really only three cases.
-            target if 2 * max < target => {
-                Err("Imminent Failure: Maximum is too small to reach the required sum.".to_string())
-            }
-            target if 2 * min > target => {
-                Err("Imminent Failure: Minimum is too big to reach the required sum.".to_string())
-            }
+            target if double_is_below(max, target) => Err(CollapseError::MaxTooSmall {
+                index,
+                target,
+                min,
+                max,
+            }),
+            target if double_is_above(min, target) => Err(CollapseError::MinTooBig {
+                index,
+                target,
+                min,
+                max,
+            }),
             _ => {
                 //Normal warning. Is c-a in the values?((
                 for (element, _) in values.iter() {
@@ -331,34 +408,45 @@ pub mod bench_iterations {
                         return Ok(());
                     }
                 } //O(n*log(n)) -> On average, a non-faulty mine will hit this case.
-                Err("Imminent Failure detected. No pairs reach the required sum.".to_string())
+                Err(CollapseError::NoPairFound {
+                    index,
+                    target,
+                    min,
+                    max,
+                })
             }
         }
     }
 
     ///Used in collapse_check_with_more_short_circuiting
-    fn pair_exists_references_with_short_circuit(
-        values: &Vec<(&u128, usize)>,
+    fn pair_exists_with_short_circuit(
+        values: &[(u128, usize)],
+        index: usize,
         target: u128,
-    ) -> Result<(), String> {
-        let mut i = 0;
-        let min = values[i].0;
+    ) -> Result<(), CollapseError> {
+        let min = values[0].0;
         let max = values[values.len() - 1].0;
         //Normal warning. Is c-a in the values?((
         for (element, _) in values.iter() {
-            if 2 * max < target {
-                return Err(
-                    "Imminent Failure: Maximum is too small to reach the required sum.".to_string(),
-                );
+            if double_is_below(max, target) {
+                return Err(CollapseError::MaxTooSmall {
+                    index,
+                    target,
+                    min,
+                    max,
+                });
             }
-            if 2 * min > target {
-                return Err(
-                    "Imminent Failure: Minimum is too big to reach the required sum.".to_string(),
-                );
+            if double_is_above(min, target) {
+                return Err(CollapseError::MinTooBig {
+                    index,
+                    target,
+                    min,
+                    max,
+                });
             }
-            let inverse = match &target >= *element {
-                true => target - **element,
-                false => **element - target,
+            let inverse = match target >= *element {
+                true => target - element,
+                false => element - target,
             };
 
             if values
@@ -367,16 +455,21 @@ pub mod bench_iterations {
             {
                 return Ok(());
             }
-            i += 1;
         } //O(n*log(n)) -> On average, a non-faulty mine will hit this case.
-        Err("Imminent Failure detected. No pairs reach the required sum.".to_string())
+        Err(CollapseError::NoPairFound {
+            index,
+            target,
+            min,
+            max,
+        })
     }
 
     ///Used in collapse_check_no_short_ciruit
     fn pair_exists_no_short_circuit(
-        values: &Vec<(u128, usize)>,
+        values: &[(u128, usize)],
+        index: usize,
         target: u128,
-    ) -> Result<(), String> {
+    ) -> Result<(), CollapseError> {
         //Normal warning. Is c-a in the values?
         for (element, _) in values.iter() {
             let inverse = match target >= *element {
@@ -391,24 +484,39 @@ pub mod bench_iterations {
                 return Ok(());
             }
         } //O(n*log(n)) -> On average, a non-faulty mine will hit this case.
-        Err("Imminent Failure detected. No pairs reach the required sum.".to_string())
+        Err(CollapseError::NoPairFound {
+            index,
+            target,
+            min: values[0].0,
+            max: values[values.len() - 1].0,
+        })
     }
 
     ///Used in collapse_check_narrowing_search
-    fn pair_exists_narrowing(values: &Vec<(u128, usize)>, target: u128) -> Result<(), String> {
-        let mut i = 0;
+    fn pair_exists_narrowing(
+        values: &[(u128, usize)],
+        index: usize,
+        target: u128,
+    ) -> Result<(), CollapseError> {
+        let min = values[0].0;
         let max = values[values.len() - 1].0;
         //Normal warning. Is c-a in the values?
-        for (element, _) in values.iter() {
-            if 2 * max < target {
-                return Err(
-                    "Imminent Failure: Maximum is too small to reach the required sum.".to_string(),
-                );
+        for (i, (element, _)) in values.iter().enumerate() {
+            if double_is_below(max, target) {
+                return Err(CollapseError::MaxTooSmall {
+                    index,
+                    target,
+                    min,
+                    max,
+                });
             }
-            if 2 * values[i].0 > target {
-                return Err(
-                    "Imminent Failure: Minimum is too big to reach the required sum.".to_string(),
-                );
+            if double_is_above(*element, target) {
+                return Err(CollapseError::MinTooBig {
+                    index,
+                    target,
+                    min,
+                    max,
+                });
             }
             let inverse = match target >= *element {
                 true => target - element,
@@ -421,8 +529,12 @@ pub mod bench_iterations {
             {
                 return Ok(());
             }
-            i += 1
         } //O(n*log(n)) -> On average, a non-faulty mine will hit this case.
-        Err("Imminent Failure detected. No pairs reach the required sum.".to_string())
+        Err(CollapseError::NoPairFound {
+            index,
+            target,
+            min,
+            max,
+        })
     }
 }
@@ -1,11 +1,596 @@
-fn main() {
-    use std::fs;
-    let numbers = fs::read_to_string("../../test_inputs/challenge_input.txt");
-    let mut numbers = Vec::from_iter(numbers.unwrap().lines().map(|x| x.parse::<u128>().unwrap()));
-
-    use namt_preventative_collapse::collapse_check;
-    match collapse_check(&mut numbers, 100) {
-        Err(error) => eprintln!("{:?}", error),
-        _ => {}
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use namt_preventative_collapse::{
+    parse_csv, parse_readings, parse_readings_lenient, CollapseError, Column, CsvFormat, Generator,
+    Header, Injection, Record, Report, Strategy, Violation,
+};
+use serde::Serialize;
+
+const USAGE: &str = "\
+Checks a series of mine readings for imminent collapse.
+
+Usage: namt_preventative_collapse [OPTIONS] <INPUT>
+       namt_preventative_collapse generate [OPTIONS] <OUTPUT>   Synthetic readings, see generate --help
+
+Arguments:
+  <INPUT>  File with one reading per line, or - to read standard input.
+           Blank lines and # comments are skipped, readings may use _ and 0x
+
+Options:
+  --period <N>         Readings each reading is checked against [default: 100]
+  --strategy <NAME>    resort, hashed or incremental [default: resort]
+  --all                Report every anomaly instead of stopping at the first
+  --format <FORMAT>    text, json, jsonl or csv [default: text]
+  --lenient            Skip malformed lines instead of stopping, and count them
+  --witnesses          Add the pair that validated each reading to json and jsonl output,
+                       when the input is safe
+  -h, --help           Print this help
+
+CSV input, with each sensor checked as its own series:
+  --csv                      Read timestamp,sensor_id,value rows. Implied by the options below
+  --delimiter <CHAR>         Field delimiter, or tab [default: ,]
+  --header <auto|yes|no>     Whether the first row names the columns [default: auto]
+  --timestamp-column <COL>   Header name or position from 1 [default: 1]
+  --sensor-column <COL>      Header name or position from 1 [default: 2]
+  --value-column <COL>       Header name or position from 1 [default: 3]
+  --window <DURATION>        Check against the readings logged in this long before, such as
+                             90s, 10m or 1h, instead of --period. Ignores --strategy
+
+Exit codes:
+  0  Safe, no collapse detected
+  1  Collapse detected
+  2  Input or usage error
+";
+
+const GENERATE_USAGE: &str = "\
+Writes a seeded series of synthetic mine readings, and the anomalies injected in it.
+
+Usage: namt_preventative_collapse generate [OPTIONS] <OUTPUT>
+
+Arguments:
+  <OUTPUT>  File to write one reading per line to, or - for standard output
+
+Options:
+  --period <N>          Readings each reading is checked against, at least 3 [default: 100]
+  --length <N>          Number of readings [default: 10000]
+  --seed <N>            The same seed and options give the same series [default: 0]
+  --max-reading <N>     Largest valid reading, from 2 to a third of the largest u128
+                        [default: 1000000]
+  --anomalies <N>       Anomalies of random kinds at random indexes [default: 0]
+  --at <INDEX:KIND>     An anomaly at INDEX, too-large, too-small or no-pair. Repeatable.
+                        Anomalies are after the first period, and more than a period apart
+  --truth <PATH>        File to write the anomalies to, as the --all --format csv report.
+                        [default: <OUTPUT> with extension .anomalies.csv, none for -]
+  -h, --help            Print this help
+";
+
+///No anomaly in the input.
+const EXIT_SAFE: u8 = 0;
+///At least one anomaly in the input.
+const EXIT_COLLAPSE: u8 = 1;
+///The input could not be read or parsed, or the arguments were invalid.
+const EXIT_INPUT_ERROR: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+    JsonLines,
+    Csv,
+}
+
+#[derive(Debug)]
+struct Options {
+    input: String,
+    period: usize,
+    ///Replaces `period`, for CSV input.
+    window: Option<Duration>,
+    strategy: Strategy,
+    all: bool,
+    format: Format,
+    lenient: bool,
+    witnesses: bool,
+    csv: Option<CsvFormat>,
+}
+
+#[derive(Debug)]
+struct GenerateOptions {
+    generator: Generator,
+    output: String,
+    truth: Option<String>,
+}
+
+///The readings to check, plain or from CSV.
+enum Input {
+    Plain(Vec<u128>),
+    Csv(Vec<Record>),
+}
+
+///The report, with the timestamp and sensor of each anomaly for CSV input.
+enum Outcome {
+    Plain(Report),
+    Csv(Report<Violation>),
+}
+
+///`None` when help was asked for.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut input = None;
+    let mut period = None;
+    let mut window = None;
+    let mut strategy = Strategy::default();
+    let mut all = false;
+    let mut format = Format::Text;
+    let mut lenient = false;
+    let mut witnesses = false;
+    let mut csv: Option<CsvFormat> = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} needs a value.", name));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--period" => {
+                period = match value("--period")?.parse() {
+                    Ok(period) if period > 0 => Some(period),
+                    _ => return Err("--period must be a whole number above zero.".to_string()),
+                }
+            }
+            "--window" => {
+                window = match duration(&value("--window")?) {
+                    Some(window) if !window.is_zero() => Some(window),
+                    _ => {
+                        return Err(
+                            "--window must be a duration above zero, such as 90s, 10m or 1h."
+                                .to_string(),
+                        )
+                    }
+                };
+                csv.get_or_insert_with(CsvFormat::default);
+            }
+            "--strategy" => {
+                strategy = match value("--strategy")?.as_str() {
+                    "resort" => Strategy::Resort,
+                    "hashed" => Strategy::Hashed,
+                    "incremental" => Strategy::Incremental,
+                    other => return Err(format!("Unknown strategy {}.", other)),
+                }
+            }
+            "--all" => all = true,
+            "--lenient" => lenient = true,
+            "--witnesses" => witnesses = true,
+            "--csv" => {
+                csv.get_or_insert_with(CsvFormat::default);
+            }
+            "--delimiter" => {
+                let delimiter = value("--delimiter")?;
+                let mut chars = delimiter.chars();
+                csv.get_or_insert_with(CsvFormat::default).delimiter =
+                    match (delimiter.as_str(), chars.next(), chars.next()) {
+                        ("tab" | "\\t", _, _) => '\t',
+                        (_, Some(delimiter), None) => delimiter,
+                        _ => return Err("--delimiter must be a single character.".to_string()),
+                    }
+            }
+            "--header" => {
+                csv.get_or_insert_with(CsvFormat::default).header =
+                    match value("--header")?.as_str() {
+                        "auto" => Header::Auto,
+                        "yes" => Header::Present,
+                        "no" => Header::Absent,
+                        other => return Err(format!("Unknown header setting {}.", other)),
+                    }
+            }
+            "--timestamp-column" => {
+                csv.get_or_insert_with(CsvFormat::default).timestamp =
+                    column(value("--timestamp-column")?)?
+            }
+            "--sensor-column" => {
+                csv.get_or_insert_with(CsvFormat::default).sensor =
+                    column(value("--sensor-column")?)?
+            }
+            "--value-column" => {
+                csv.get_or_insert_with(CsvFormat::default).value = column(value("--value-column")?)?
+            }
+            "--format" => {
+                format = match value("--format")?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "jsonl" => Format::JsonLines,
+                    "csv" => Format::Csv,
+                    other => return Err(format!("Unknown format {}.", other)),
+                }
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}.", flag)),
+            path if input.is_none() => input = Some(path.to_string()),
+            path => return Err(format!("Unexpected argument {}.", path)),
+        }
+    }
+    let input = input.ok_or("Missing <INPUT>.")?;
+    if period.is_some() && window.is_some() {
+        return Err("--period and --window cannot be used together.".to_string());
+    }
+    if lenient && csv.is_some() {
+        return Err("--lenient only applies to plain readings.".to_string());
+    }
+    if witnesses && csv.is_some() {
+        return Err("--witnesses only applies to plain readings.".to_string());
+    }
+    Ok(Some(Options {
+        input,
+        period: period.unwrap_or(100),
+        window,
+        strategy,
+        all,
+        format,
+        lenient,
+        witnesses,
+        csv,
+    }))
+}
+
+///`None` when help was asked for.
+fn parse_generate_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<Option<GenerateOptions>, String> {
+    let mut generator = Generator::new(100, 10_000);
+    let mut output = None;
+    let mut truth = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} needs a value.", name));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--period" => {
+                generator.period = match value("--period")?.parse() {
+                    Ok(period) if period >= 3 => period,
+                    _ => return Err("--period must be a whole number of at least 3.".to_string()),
+                }
+            }
+            "--length" => {
+                generator.len = value("--length")?
+                    .parse()
+                    .map_err(|_| "--length must be a whole number.".to_string())?
+            }
+            "--seed" => {
+                generator.seed = value("--seed")?
+                    .parse()
+                    .map_err(|_| "--seed must be a whole number.".to_string())?
+            }
+            "--max-reading" => {
+                generator.max_reading = match value("--max-reading")?.parse() {
+                    Ok(max_reading) if (2..=Generator::MAX_READING).contains(&max_reading) => {
+                        max_reading
+                    }
+                    _ => {
+                        return Err(format!(
+                            "--max-reading must be a whole number from 2 to {}.",
+                            Generator::MAX_READING
+                        ))
+                    }
+                }
+            }
+            "--anomalies" => {
+                generator.random_anomalies = value("--anomalies")?
+                    .parse()
+                    .map_err(|_| "--anomalies must be a whole number.".to_string())?
+            }
+            "--at" => generator.anomalies.push(injection(&value("--at")?)?),
+            "--truth" => truth = Some(value("--truth")?),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}.", flag)),
+            path if output.is_none() => output = Some(path.to_string()),
+            path => return Err(format!("Unexpected argument {}.", path)),
+        }
+    }
+    let output: String = output.ok_or("Missing <OUTPUT>.")?;
+    let (period, len) = (generator.period, generator.len);
+    let mut indexes: Vec<usize> = generator
+        .anomalies
+        .iter()
+        .map(|(index, _)| *index)
+        .collect();
+    indexes.sort_unstable();
+    if let Some(index) = indexes.iter().find(|index| !(period..len).contains(index)) {
+        return Err(format!(
+            "--at {} is not a checked reading, which are {} to {}.",
+            index,
+            period,
+            len.saturating_sub(1)
+        ));
+    }
+    if let Some(pair) = indexes.windows(2).find(|pair| pair[1] - pair[0] <= period) {
+        return Err(format!(
+            "--at {} and {} must be more than the period apart.",
+            pair[0], pair[1]
+        ));
+    }
+    if truth.is_none() && output != "-" {
+        truth = Some(
+            Path::new(&output)
+                .with_extension("anomalies.csv")
+                .to_string_lossy()
+                .into_owned(),
+        );
+    }
+    Ok(Some(GenerateOptions {
+        generator,
+        output,
+        truth,
+    }))
+}
+
+///`120:too-large`, an index and a kind of anomaly.
+fn injection(value: &str) -> Result<(usize, Injection), String> {
+    let invalid = || format!("--at {} must be INDEX:KIND, such as 120:no-pair.", value);
+    let (index, kind) = value.split_once(':').ok_or_else(invalid)?;
+    let index = index.parse().map_err(|_| invalid())?;
+    let kind = match kind {
+        "too-large" => Injection::TooLarge,
+        "too-small" => Injection::TooSmall,
+        "no-pair" => Injection::NoPair,
+        other => return Err(format!("Unknown anomaly kind {}.", other)),
+    };
+    Ok((index, kind))
+}
+
+///Writes the readings to `output` and the anomalies to `truth`.
+fn generate(options: &GenerateOptions) -> Result<(), String> {
+    let synthetic = options.generator.generate();
+    let written = create(&options.output).and_then(|mut out| {
+        synthetic.write_readings(&mut out)?;
+        out.flush()
+    });
+    written.map_err(|error| format!("Could not write {}: {}", options.output, error))?;
+    if let Some(truth) = &options.truth {
+        let written = create(truth).and_then(|mut out| {
+            synthetic.write_anomalies(&mut out)?;
+            out.flush()
+        });
+        written.map_err(|error| format!("Could not write {}: {}", truth, error))?;
+    }
+    Ok(())
+}
+
+///The file at `path`, or standard output for `-`, buffered.
+fn create(path: &str) -> io::Result<BufWriter<Box<dyn Write>>> {
+    let out: Box<dyn Write> = match path {
+        "-" => Box::new(io::stdout()),
+        path => Box::new(fs::File::create(path)?),
+    };
+    Ok(BufWriter::new(out))
+}
+
+fn generate_main(args: impl Iterator<Item = String>) -> ExitCode {
+    let options = match parse_generate_args(args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{}", GENERATE_USAGE);
+            return ExitCode::from(EXIT_SAFE);
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, GENERATE_USAGE);
+            return ExitCode::from(EXIT_INPUT_ERROR);
+        }
+    };
+    match generate(&options) {
+        Ok(()) => ExitCode::from(EXIT_SAFE),
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::from(EXIT_INPUT_ERROR)
+        }
+    }
+}
+
+///`90s`, `10m`, `1h` or `500ms`. Bare numbers are seconds.
+fn duration(value: &str) -> Option<Duration> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let count: u64 = value[..split].parse().ok()?;
+    match &value[split..] {
+        "" | "s" => Some(Duration::from_secs(count)),
+        "ms" => Some(Duration::from_millis(count)),
+        "m" => Some(Duration::from_secs(count.checked_mul(60)?)),
+        "h" => Some(Duration::from_secs(count.checked_mul(3600)?)),
+        _ => None,
+    }
+}
+
+///A CSV column from a position counting from 1, or a header name.
+fn column(value: String) -> Result<Column, String> {
+    match value.parse::<usize>() {
+        Ok(0) => Err("Column positions count from 1.".to_string()),
+        Ok(position) => Ok(Column::Index(position - 1)),
+        Err(_) => Ok(Column::Name(value)),
+    }
+}
+
+fn read_input(options: &Options) -> Result<Input, String> {
+    let text = match options.input.as_str() {
+        "-" => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|error| format!("Could not read standard input: {}", error))?;
+            text
+        }
+        path => fs::read_to_string(path)
+            .map_err(|error| format!("Could not read {}: {}", path, error))?,
+    };
+    match (&options.csv, options.lenient) {
+        (Some(format), _) => parse_csv(&text, format)
+            .map(Input::Csv)
+            .map_err(|error| error.to_string()),
+        (None, false) => parse_readings(&text)
+            .map(Input::Plain)
+            .map_err(|error| error.to_string()),
+        (None, true) => {
+            let parsed = parse_readings_lenient(&text);
+            for error in &parsed.skipped {
+                eprintln!("Skipped. {}", error);
+            }
+            if !parsed.skipped.is_empty() {
+                eprintln!("Skipped {} malformed lines.", parsed.skipped.len());
+            }
+            Ok(Input::Plain(parsed.readings))
+        }
+    }
+}
+
+fn detect(options: &Options, input: &Input) -> Outcome {
+    let (strategy, period, all) = (options.strategy, options.period, options.all);
+    match input {
+        Input::Plain(readings) => {
+            let report = Report::new(readings, period, strategy, all);
+            Outcome::Plain(match options.witnesses {
+                true => report.with_witnesses(readings),
+                false => report,
+            })
+        }
+        Input::Csv(records) => Outcome::Csv(match options.window {
+            Some(window) => Report::records_timed(records, window, all),
+            None => Report::records(records, period, strategy, all),
+        }),
+    }
+}
+
+///One line per anomaly, then one per witness, then the summary.
+fn json_lines<A: Serialize>(out: &mut impl Write, report: &Report<A>) -> io::Result<()> {
+    for anomaly in &report.anomalies {
+        serde_json::to_writer(&mut *out, anomaly)?;
+        writeln!(out)?;
+    }
+    for witness in &report.witnesses {
+        serde_json::to_writer(&mut *out, witness)?;
+        writeln!(out)?;
+    }
+    serde_json::to_writer(&mut *out, &report.summary)?;
+    writeln!(out)
+}
+
+///Quotes a field for CSV output, when it needs it.
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+
+fn report(out: &mut impl Write, options: &Options, outcome: &Outcome) -> io::Result<()> {
+    //Timestamp and sensor, for CSV input, and the error of each anomaly.
+    let (summary, rows): (_, Vec<(Option<&Violation>, &CollapseError)>) = match outcome {
+        Outcome::Plain(report) => (
+            &report.summary,
+            report.anomalies.iter().map(|error| (None, error)).collect(),
+        ),
+        Outcome::Csv(report) => (
+            &report.summary,
+            report
+                .anomalies
+                .iter()
+                .map(|violation| (Some(violation), &violation.error))
+                .collect(),
+        ),
+    };
+    let (readings, sensors) = (summary.readings, summary.sensors);
+    match options.format {
+        Format::Text => {
+            for row in &rows {
+                match row {
+                    (Some(violation), _) => writeln!(out, "{}", violation)?,
+                    (None, error) => writeln!(out, "{}", error)?,
+                }
+            }
+            if rows.is_empty() {
+                write!(out, "Safe. No collapse detected in {} readings", readings)?;
+                if let Some(sensors) = sensors {
+                    write!(out, " from {} sensors", sensors)?;
+                }
+                match options.window {
+                    Some(window) => writeln!(out, " with window {:?}.", window)?,
+                    None => writeln!(out, " with period {}.", options.period)?,
+                }
+            }
+        }
+        Format::Json => {
+            match outcome {
+                Outcome::Plain(report) => serde_json::to_writer(&mut *out, report)?,
+                Outcome::Csv(report) => serde_json::to_writer(&mut *out, report)?,
+            }
+            writeln!(out)?;
+        }
+        Format::JsonLines => match outcome {
+            Outcome::Plain(report) => json_lines(out, report)?,
+            Outcome::Csv(report) => json_lines(out, report)?,
+        },
+        Format::Csv => {
+            if sensors.is_some() {
+                write!(out, "timestamp,sensor,")?;
+            }
+            writeln!(out, "index,kind,target,min,max")?;
+            for (violation, anomaly) in &rows {
+                if let Some(violation) = violation {
+                    write!(
+                        out,
+                        "{},{},",
+                        csv_field(&violation.timestamp),
+                        csv_field(&violation.sensor)
+                    )?;
+                }
+                let (min, max) = match anomaly.window() {
+                    Some((min, max)) => (min.to_string(), max.to_string()),
+                    None => (String::new(), String::new()),
+                };
+                writeln!(
+                    out,
+                    "{},{},{},{},{}",
+                    anomaly.index(),
+                    anomaly.kind(),
+                    anomaly.target(),
+                    min,
+                    max
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) == Some("generate") {
+        return generate_main(args.skip(1));
+    }
+    let options = match parse_args(args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return ExitCode::from(EXIT_SAFE);
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return ExitCode::from(EXIT_INPUT_ERROR);
+        }
+    };
+    let input = match read_input(&options) {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::from(EXIT_INPUT_ERROR);
+        }
+    };
+
+    let outcome = detect(&options, &input);
+    if let Err(error) = report(&mut io::stdout().lock(), &options, &outcome) {
+        eprintln!("Could not write the report: {}", error);
+        return ExitCode::from(EXIT_INPUT_ERROR);
+    }
+    let safe = match &outcome {
+        Outcome::Plain(report) => report.summary.safe,
+        Outcome::Csv(report) => report.summary.safe,
+    };
+    match safe {
+        true => ExitCode::from(EXIT_SAFE),
+        false => ExitCode::from(EXIT_COLLAPSE),
     }
 }
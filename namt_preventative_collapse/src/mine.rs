@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use crate::{CollapseError, CollapseMonitor, CollapseStrategy, ResortWindow, Verdict};
+
+/// A [`CollapseMonitor`] for every sensor of a mine, for a collector that receives readings from all of them.
+///
+/// Readings are routed by sensor id to independent windows, so one sensor's readings are never checked against
+/// another's. Sensors start a window the first time they report, with the default `period` unless given their own
+/// with `add_sensor`. The monitor keeps the latest verdict of every sensor, and `status` aggregates them.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{MineMonitor, Verdict};
+/// let mut mine = MineMonitor::new(3);
+/// mine.add_sensor("B7", 2);
+/// for reading in [1, 2, 3] {
+///     mine.push("B7", reading).unwrap();
+///     mine.push("C2", reading).unwrap();
+/// }
+/// assert_eq!(mine.push("B7", 100).is_err(), true);
+/// assert_eq!(mine.push("C2", 4), Ok(Verdict::Safe));
+///
+/// let status = mine.status();
+/// assert_eq!(status.failing, vec!["B7".to_string()]);
+/// assert_eq!(status.safe, 1);
+/// assert_eq!(status.is_safe(), false);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MineMonitor<S: CollapseStrategy = ResortWindow> {
+    default_period: usize,
+    sensors: BTreeMap<String, Channel<S>>,
+}
+
+///One sensor's window and its latest verdict.
+#[derive(Debug, Clone)]
+struct Channel<S: CollapseStrategy> {
+    monitor: CollapseMonitor<S>,
+    latest: Result<Verdict, CollapseError<S::Reading>>,
+}
+
+impl<S: CollapseStrategy> Channel<S> {
+    fn new(period: usize) -> Self {
+        Channel {
+            monitor: CollapseMonitor::with_strategy(period),
+            latest: Ok(Verdict::Warming),
+        }
+    }
+}
+
+/// The mine-wide status, aggregated over the latest verdict of every sensor.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MineStatus {
+    /// Sensors whose window is still filling up.
+    pub warming: usize,
+    /// Sensors whose latest reading is safe.
+    pub safe: usize,
+    /// Sensors whose latest reading failed the collapse check, by id in ascending order.
+    pub failing: Vec<String>,
+}
+
+impl MineStatus {
+    /// `true` unless a sensor's latest reading failed. Warming sensors count as safe.
+    pub fn is_safe(&self) -> bool {
+        self.failing.is_empty()
+    }
+
+    /// Number of sensors that have reported or were added.
+    pub fn sensors(&self) -> usize {
+        self.warming + self.safe + self.failing.len()
+    }
+}
+
+impl MineMonitor {
+    /// Creates a monitor that checks each sensor's readings against the `default_period` readings before them,
+    /// unless the sensor is given its own period.
+    /// ### Panics
+    /// If `default_period` is zero. There is no pair in an empty window.
+    pub fn new(default_period: usize) -> Self {
+        Self::with_strategy(default_period)
+    }
+}
+
+impl<S: CollapseStrategy> MineMonitor<S> {
+    /// Same as `new`, with the pair search of every sensor chosen at compile time.
+    /// ### Panics
+    /// If `default_period` is zero. There is no pair in an empty window.
+    pub fn with_strategy(default_period: usize) -> Self {
+        assert!(
+            default_period > 0,
+            "A period of zero leaves nothing to check against."
+        );
+        MineMonitor {
+            default_period,
+            sensors: BTreeMap::new(),
+        }
+    }
+
+    /// Gives `sensor` its own `period`. Starts a new, empty window, so any readings the sensor already reported are
+    /// forgotten.
+    /// ### Panics
+    /// If `period` is zero. There is no pair in an empty window.
+    pub fn add_sensor(&mut self, sensor: impl Into<String>, period: usize) {
+        self.sensors.insert(sensor.into(), Channel::new(period));
+    }
+
+    /// Checks `reading` against the window of `sensor`, then slides that window forward.
+    /// The index in any error counts the readings of this sensor only, starting at zero.
+    pub fn push(
+        &mut self,
+        sensor: &str,
+        reading: S::Reading,
+    ) -> Result<Verdict, CollapseError<S::Reading>> {
+        if !self.sensors.contains_key(sensor) {
+            self.add_sensor(sensor, self.default_period);
+        }
+        let channel = self.sensors.get_mut(sensor).unwrap();
+        channel.latest = channel.monitor.push(reading);
+        channel.latest.clone()
+    }
+
+    /// The latest verdict of `sensor`, or `None` if it never reported and was never added.
+    pub fn verdict(&self, sensor: &str) -> Option<&Result<Verdict, CollapseError<S::Reading>>> {
+        self.sensors.get(sensor).map(|channel| &channel.latest)
+    }
+
+    /// The latest verdict of every sensor, by id in ascending order.
+    pub fn verdicts(
+        &self,
+    ) -> impl Iterator<Item = (&str, &Result<Verdict, CollapseError<S::Reading>>)> {
+        self.sensors
+            .iter()
+            .map(|(sensor, channel)| (sensor.as_str(), &channel.latest))
+    }
+
+    /// The window behind `sensor`, to read its period and how many readings it has seen.
+    pub fn sensor(&self, sensor: &str) -> Option<&CollapseMonitor<S>> {
+        self.sensors.get(sensor).map(|channel| &channel.monitor)
+    }
+
+    /// Aggregates the latest verdict of every sensor.
+    pub fn status(&self) -> MineStatus {
+        let mut status = MineStatus::default();
+        for (sensor, verdict) in self.verdicts() {
+            match verdict {
+                Ok(Verdict::Warming) => status.warming += 1,
+                Ok(Verdict::Safe) => status.safe += 1,
+                Err(_) => status.failing.push(sensor.to_string()),
+            }
+        } //O(sensors)
+        status
+    }
+
+    /// Period of the windows of sensors not given their own.
+    pub fn default_period(&self) -> usize {
+        self.default_period
+    }
+
+    /// Number of sensors that have reported or were added.
+    pub fn len(&self) -> usize {
+        self.sensors.len()
+    }
+
+    /// `true` until a sensor reports or is added.
+    pub fn is_empty(&self) -> bool {
+        self.sensors.is_empty()
+    }
+}
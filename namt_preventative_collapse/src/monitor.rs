@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+
+use crate::{CollapseError, CollapseObserver, CollapseStrategy, ResortWindow};
+
+/// Outcome of a reading that did not fail the collapse check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Verdict {
+    /// The window is still filling up. The reading was stored, but not checked.
+    Warming,
+    /// The reading was checked against a full window and is safe.
+    Safe,
+}
+
+/// Streaming version of `collapse_check`, for hardware that receives one reading at a time.
+///
+/// Keeps the last `period` readings internally and checks every new reading against them.
+/// A failing reading still enters the window, so the monitor reaches the same verdicts as `collapse_scan`.
+/// The pair search is a [`CollapseStrategy`], `collapse_check`'s [`ResortWindow`] unless chosen with `with_strategy`,
+/// which also decides the [`Reading`](crate::Reading) type.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{CollapseMonitor, Verdict};
+/// let mut monitor = CollapseMonitor::new(3);
+/// assert_eq!(monitor.push(1), Ok(Verdict::Warming));
+/// assert_eq!(monitor.push(2), Ok(Verdict::Warming));
+/// assert_eq!(monitor.push(3), Ok(Verdict::Warming));
+/// assert_eq!(monitor.push(4), Ok(Verdict::Safe));
+/// assert!(monitor.push(100).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CollapseMonitor<S: CollapseStrategy = ResortWindow> {
+    period: usize,
+    window: VecDeque<S::Reading>,
+    strategy: Option<S>,
+    index: usize,
+    anomalies: usize,
+}
+
+/// The state of a [`CollapseMonitor`], saved so a collector that restarts resumes checking where it stopped,
+/// instead of spending the first `period` readings warming up again.
+///
+/// The window is kept as readings, oldest first. Every strategy builds its search state from them, so a resumed
+/// monitor reaches exactly the verdicts the original would have. With the `serde` feature, a checkpoint is
+/// `Serialize` and `Deserialize`.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{CollapseMonitor, Verdict};
+/// let mut monitor = CollapseMonitor::new(3);
+/// for reading in [1, 2, 3] {
+///     monitor.push(reading).unwrap();
+/// }
+/// let checkpoint = monitor.checkpoint();
+/// assert_eq!(checkpoint.window, vec![1, 2, 3]);
+///
+/// let mut resumed = CollapseMonitor::resume(checkpoint).unwrap();
+/// assert_eq!(resumed.push(4), Ok(Verdict::Safe));
+/// assert_eq!(resumed.len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint<T = u128> {
+    /// Number of readings each new reading is checked against.
+    pub period: usize,
+    /// Number of readings pushed so far, which is also the index of the next one.
+    pub index: usize,
+    /// Number of readings that failed the collapse check so far.
+    pub anomalies: usize,
+    /// The last `period` readings, or every reading while the window is filling up, oldest first.
+    pub window: Vec<T>,
+}
+
+impl CollapseMonitor {
+    /// Creates a monitor that checks each reading against the `period` readings before it.
+    /// ### Panics
+    /// If `period` is zero. There is no pair in an empty window.
+    pub fn new(period: usize) -> Self {
+        Self::with_strategy(period)
+    }
+
+    /// Restores a monitor from a `checkpoint`, saved by `checkpoint`.
+    ///
+    /// Returns `None` if the checkpoint is inconsistent: a period of zero, a window that is not the last `period`
+    /// readings, or more anomalies than checked readings.
+    pub fn resume(checkpoint: Checkpoint) -> Option<Self> {
+        Self::resume_with_strategy(checkpoint)
+    }
+}
+
+impl<S: CollapseStrategy> CollapseMonitor<S> {
+    /// Same as `new`, with the pair search chosen at compile time.
+    /// ### Example
+    /// ```
+    /// use namt_preventative_collapse::{CollapseMonitor, HashedWindow};
+    /// let mut monitor = CollapseMonitor::<HashedWindow>::with_strategy(3);
+    /// for reading in [1, 2, 3, 4] {
+    ///     assert!(monitor.push(reading).is_ok());
+    /// }
+    /// ```
+    /// ### Panics
+    /// If `period` is zero. There is no pair in an empty window.
+    pub fn with_strategy(period: usize) -> Self {
+        assert!(
+            period > 0,
+            "A period of zero leaves nothing to check against."
+        );
+        CollapseMonitor {
+            period,
+            window: VecDeque::with_capacity(period),
+            strategy: None,
+            index: 0,
+            anomalies: 0,
+        }
+    }
+
+    /// Same as `resume`, with the pair search chosen at compile time. It may differ from the one that saved the
+    /// checkpoint.
+    pub fn resume_with_strategy(checkpoint: Checkpoint<S::Reading>) -> Option<Self> {
+        let Checkpoint {
+            period,
+            index,
+            anomalies,
+            window,
+        } = checkpoint;
+        let checked = index.saturating_sub(period);
+        if period == 0 || window.len() != index.min(period) || anomalies > checked {
+            return None;
+        }
+        let mut window = VecDeque::from(window);
+        let strategy = match window.len() == period {
+            true => Some(S::from_preamble(window.make_contiguous())),
+            false => None,
+        };
+        Some(CollapseMonitor {
+            period,
+            window,
+            strategy,
+            index,
+            anomalies,
+        })
+    }
+
+    /// Saves the state of the monitor, to `resume` it later.
+    pub fn checkpoint(&self) -> Checkpoint<S::Reading> {
+        Checkpoint {
+            period: self.period,
+            index: self.index,
+            anomalies: self.anomalies,
+            window: self.window.iter().copied().collect(),
+        }
+    }
+
+    /// Checks `reading` against the window, then slides the window forward.
+    /// The index in any error counts every reading pushed so far, starting at zero.
+    pub fn push(&mut self, reading: S::Reading) -> Result<Verdict, CollapseError<S::Reading>> {
+        let index = self.index;
+        self.index += 1;
+        let strategy = match &mut self.strategy {
+            Some(strategy) => strategy,
+            None => {
+                self.window.push_back(reading);
+                if self.window.len() == self.period {
+                    self.strategy = Some(S::from_preamble(self.window.make_contiguous()));
+                }
+                return Ok(Verdict::Warming);
+            }
+        };
+
+        let result = strategy.check(index, reading);
+        let expired = self.window.pop_front().unwrap();
+        strategy.slide(expired, reading);
+        self.window.push_back(reading);
+        if result.is_err() {
+            self.anomalies += 1;
+        }
+        result.map(|_| Verdict::Safe)
+    }
+
+    /// `push`, reporting its events to `observer`. Warming readings fill the window without advancing it.
+    pub fn push_observed<O: CollapseObserver<S::Reading> + ?Sized>(
+        &mut self,
+        reading: S::Reading,
+        observer: &mut O,
+    ) -> Result<Verdict, CollapseError<S::Reading>> {
+        let index = self.index;
+        //The reading that leaves, once the window is full.
+        let expired = self.strategy.as_ref().and(self.window.front().copied());
+        let result = self.push(reading);
+        if let Err(error) = &result {
+            observer.on_anomaly(error);
+        }
+        if let Some(expired) = expired {
+            observer.on_window_advance(index, expired, reading);
+        }
+        result
+    }
+
+    /// Number of readings each new reading is checked against.
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Number of readings pushed so far, which is also the index of the next one.
+    pub fn len(&self) -> usize {
+        self.index
+    }
+
+    /// Number of readings that failed the collapse check so far.
+    pub fn anomalies(&self) -> usize {
+        self.anomalies
+    }
+
+    /// `true` until the first reading is pushed.
+    pub fn is_empty(&self) -> bool {
+        self.index == 0
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{CollapseError, CollapseStrategy, Reading, ResortWindow};
+
+/// Receives the events of a check as it walks a series, for host applications that route them to their own logs,
+/// metrics or alarms. The library itself never prints.
+///
+/// Both methods do nothing by default, so an observer implements only the events it cares about. Any closure that
+/// takes a `&CollapseError` is an observer of anomalies, and `()` observes nothing.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_scan_observed, CollapseError, CollapseObserver};
+/// #[derive(Default)]
+/// struct Counter {
+///     anomalies: usize,
+///     advances: usize,
+/// }
+/// impl CollapseObserver for Counter {
+///     fn on_anomaly(&mut self, _error: &CollapseError) {
+///         self.anomalies += 1;
+///     }
+///     fn on_window_advance(&mut self, _index: usize, _expired: u128, _incoming: u128) {
+///         self.advances += 1;
+///     }
+/// }
+/// let mut counter = Counter::default();
+/// collapse_scan_observed(&[1, 1, 1, 4, 2, 9], 3, &mut counter);
+/// assert_eq!((counter.anomalies, counter.advances), (2, 3));
+/// ```
+pub trait CollapseObserver<T = u128> {
+    /// A reading failed the collapse check. Called before the reading enters the window.
+    fn on_anomaly(&mut self, _error: &CollapseError<T>) {}
+
+    /// The window slid forward past the checked reading at `index`: `incoming` entered it and `expired` left.
+    fn on_window_advance(&mut self, _index: usize, _expired: T, _incoming: T) {}
+}
+
+impl<T> CollapseObserver<T> for () {}
+
+impl<T, F: FnMut(&CollapseError<T>)> CollapseObserver<T> for F {
+    fn on_anomaly(&mut self, error: &CollapseError<T>) {
+        self(error)
+    }
+}
+
+/// `collapse_check`, reporting its events to `observer`.
+/// ### Arguments
+/// * `vector:` A vector
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// * `observer:` Receives the anomaly, if any, and every advance of the window before it.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::collapse_check_observed;
+/// let mut logged = vec![];
+/// let result = collapse_check_observed(&[1, 1, 1, 4, 2, 9], 3, &mut |error: &_| logged.push(format!("{}", error)));
+/// assert!(result.is_err());
+/// assert_eq!(logged.len(), 1);
+/// ```
+pub fn collapse_check_observed<T: Reading, O: CollapseObserver<T> + ?Sized>(
+    vector: &[T],
+    period: usize,
+    observer: &mut O,
+) -> Result<(), CollapseError<T>> {
+    check_observed::<ResortWindow<T>, O>(vector, period, observer)
+}
+
+/// `collapse_scan`, reporting its events to `observer`.
+pub fn collapse_scan_observed<T: Reading, O: CollapseObserver<T> + ?Sized>(
+    vector: &[T],
+    period: usize,
+    observer: &mut O,
+) -> Vec<CollapseError<T>> {
+    scan_observed::<ResortWindow<T>, O>(vector, period, observer)
+}
+
+///The loop behind `collapse_check_with`, which observes nothing.
+pub(crate) fn check_observed<S: CollapseStrategy, O: CollapseObserver<S::Reading> + ?Sized>(
+    vector: &[S::Reading],
+    period: usize,
+    observer: &mut O,
+) -> Result<(), CollapseError<S::Reading>> {
+    if vector.len() <= period {
+        return Ok(()); //Safe.
+    }
+    let mut strategy = S::from_preamble(&vector[..period]);
+    for (c, &target) in vector.iter().enumerate().skip(period) {
+        if let Err(error) = strategy.check(c, target) {
+            observer.on_anomaly(&error);
+            return Err(error);
+        }
+        strategy.slide(vector[c - period], target);
+        observer.on_window_advance(c, vector[c - period], target);
+    }
+    Ok(())
+}
+
+///The loop behind `collapse_scan_with`, which observes nothing.
+pub(crate) fn scan_observed<S: CollapseStrategy, O: CollapseObserver<S::Reading> + ?Sized>(
+    vector: &[S::Reading],
+    period: usize,
+    observer: &mut O,
+) -> Vec<CollapseError<S::Reading>> {
+    let mut anomalies = vec![];
+    if vector.len() <= period {
+        return anomalies; //Safe.
+    }
+    let mut strategy = S::from_preamble(&vector[..period]);
+    for (c, &target) in vector.iter().enumerate().skip(period) {
+        if let Err(error) = strategy.check(c, target) {
+            observer.on_anomaly(&error);
+            anomalies.push(error);
+        }
+        strategy.slide(vector[c - period], target);
+        observer.on_window_advance(c, vector[c - period], target);
+    }
+    anomalies
+}
+
+/// Sends anomalies to the [`log`](https://docs.rs/log) crate at `warn` level, and window advances at `trace`.
+/// Requires the `log` feature.
+#[cfg(feature = "log")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogObserver;
+
+#[cfg(feature = "log")]
+impl<T: Copy + std::fmt::Display> CollapseObserver<T> for LogObserver {
+    fn on_anomaly(&mut self, error: &CollapseError<T>) {
+        log::warn!("{}", error);
+    }
+
+    fn on_window_advance(&mut self, index: usize, expired: T, incoming: T) {
+        log::trace!(
+            "Index: {}, Expired: {}, Incoming: {}",
+            index,
+            expired,
+            incoming
+        );
+    }
+}
+
+/// Sends anomalies to the [`tracing`](https://docs.rs/tracing) crate as `warn` events, with the index and kind as
+/// fields, and window advances as `trace` events. Requires the `tracing` feature.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingObserver;
+
+#[cfg(feature = "tracing")]
+impl<T: Copy + std::fmt::Display> CollapseObserver<T> for TracingObserver {
+    fn on_anomaly(&mut self, error: &CollapseError<T>) {
+        tracing::warn!(index = error.index(), kind = error.kind(), "{}", error);
+    }
+
+    fn on_window_advance(&mut self, index: usize, expired: T, incoming: T) {
+        tracing::trace!(index, %expired, %incoming, "Window advanced.");
+    }
+}
@@ -0,0 +1,122 @@
+use std::ops::Range;
+
+use rayon::prelude::*;
+
+use crate::{CollapseError, CollapseStrategy, Reading, ResortWindow};
+
+/// `collapse_check` on every core, for re-auditing archived logs.
+///
+/// Each reading only depends on the `period` readings before it, so the input is split into chunks that overlap by
+/// `period` readings and checked in parallel. The result is the same as `collapse_check`: the first anomaly, by index.
+/// Requires the `rayon` feature.
+/// ### Arguments
+/// * `vector:` A vector
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check, collapse_check_parallel};
+/// let vector: Vec<u128> = (1..=10_000).collect();
+/// assert_eq!(collapse_check_parallel(&vector, 25), collapse_check(&vector, 25));
+/// ```
+/// ### Panics
+/// If `period` is zero.
+pub fn collapse_check_parallel<T: Reading + Send + Sync>(
+    vector: &[T],
+    period: usize,
+) -> Result<(), CollapseError<T>> {
+    collapse_check_parallel_with::<ResortWindow<T>>(vector, period)
+}
+
+/// `collapse_scan` on every core. Anomalies are merged in order, so the result is the same as `collapse_scan`.
+/// Requires the `rayon` feature.
+/// ### Panics
+/// If `period` is zero.
+pub fn collapse_scan_parallel<T: Reading + Send + Sync>(
+    vector: &[T],
+    period: usize,
+) -> Vec<CollapseError<T>> {
+    collapse_scan_parallel_with::<ResortWindow<T>>(vector, period)
+}
+
+/// `collapse_check_parallel`, with the pair search chosen at compile time.
+/// ### Panics
+/// If `period` is zero.
+pub fn collapse_check_parallel_with<S: CollapseStrategy>(
+    vector: &[S::Reading],
+    period: usize,
+) -> Result<(), CollapseError<S::Reading>>
+where
+    S::Reading: Send + Sync,
+{
+    match chunks(vector.len(), period)
+        .into_par_iter()
+        .find_map_first(|targets| check_chunk::<S>(vector, period, targets).err())
+    {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// `collapse_scan_parallel`, with the pair search chosen at compile time.
+/// ### Panics
+/// If `period` is zero.
+pub fn collapse_scan_parallel_with<S: CollapseStrategy>(
+    vector: &[S::Reading],
+    period: usize,
+) -> Vec<CollapseError<S::Reading>>
+where
+    S::Reading: Send + Sync,
+{
+    chunks(vector.len(), period)
+        .into_par_iter()
+        .flat_map_iter(|targets| scan_chunk::<S>(vector, period, targets))
+        .collect()
+}
+
+///Splits the checked indexes `period..len` into ranges, a few per thread, and never much shorter than the overlap.
+fn chunks(len: usize, period: usize) -> Vec<Range<usize>> {
+    assert!(
+        period > 0,
+        "A period of zero leaves nothing to check against."
+    );
+    let checked = len.saturating_sub(period);
+    let size = checked
+        .div_ceil(rayon::current_num_threads() * 4)
+        .max(period * 4)
+        .max(1);
+    (period..len)
+        .step_by(size)
+        .map(|start| start..(start + size).min(len))
+        .collect()
+}
+
+///Checks the readings at `targets`, against a window built from the `period` readings before the first.
+fn check_chunk<S: CollapseStrategy>(
+    vector: &[S::Reading],
+    period: usize,
+    targets: Range<usize>,
+) -> Result<(), CollapseError<S::Reading>> {
+    let mut strategy = S::from_preamble(&vector[targets.start - period..targets.start]);
+    for c in targets {
+        strategy.check(c, vector[c])?;
+        strategy.slide(vector[c - period], vector[c]);
+    }
+    Ok(())
+}
+
+///`check_chunk`, carrying on past anomalies.
+fn scan_chunk<S: CollapseStrategy>(
+    vector: &[S::Reading],
+    period: usize,
+    targets: Range<usize>,
+) -> Vec<CollapseError<S::Reading>> {
+    let mut anomalies = vec![];
+    let mut strategy = S::from_preamble(&vector[targets.start - period..targets.start]);
+    for c in targets {
+        if let Err(error) = strategy.check(c, vector[c]) {
+            anomalies.push(error);
+        }
+        strategy.slide(vector[c - period], vector[c]);
+    }
+    anomalies
+}
@@ -0,0 +1,126 @@
+use std::num::IntErrorKind;
+
+use crate::{ParseError, ParseErrorKind, Reading};
+
+/// Parses readings, one per line, as logged by mine hardware.
+///
+/// Whitespace around a reading is trimmed, so Windows line endings parse. Blank lines and `#` comments, on their
+/// own line or after a reading, are skipped. Readings can use `_` as a digit separator, and a `0x` prefix for hex.
+/// Signed reading types also take a leading `-` or `+`.
+/// ### Arguments
+/// * `text:` The whole input, such as the contents of a file.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::parse_readings;
+/// let text = "# Sensor 4, shaft B\r\n35\r\n\r\n1_000_000\r\n0xff # Recalibrated\r\n";
+/// assert_eq!(parse_readings::<u128>(text), Ok(vec![35, 1_000_000, 255]));
+/// ```
+/// ### Errors
+/// The first line that is not a reading, with the line and column of the offending character.
+pub fn parse_readings<T: Reading>(text: &str) -> Result<Vec<T>, ParseError> {
+    parse_readings_iter(text).collect()
+}
+
+/// `parse_readings`, one reading at a time as the iterator is advanced, for inputs too large to collect.
+/// A malformed line yields its error, and the iterator carries on after it.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check_iter, parse_readings_iter};
+/// let text = "1\n2\n3\n4\n";
+/// //Stops at the first line that is not a reading.
+/// let readings = parse_readings_iter::<u128>(text).map_while(Result::ok);
+/// assert_eq!(collapse_check_iter(readings, 3), Ok(()));
+/// ```
+pub fn parse_readings_iter<T: Reading>(
+    text: &str,
+) -> impl Iterator<Item = Result<T, ParseError>> + '_ {
+    text.lines()
+        .enumerate()
+        .filter_map(|(line, content)| parse_line(line + 1, content).transpose())
+}
+
+/// The result of [`parse_readings_lenient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LenientReadings<T = u128> {
+    /// Every reading that parsed, in order.
+    pub readings: Vec<T>,
+    /// Every line that did not, in order.
+    pub skipped: Vec<ParseError>,
+}
+
+/// `parse_readings`, but lines that are not readings are skipped instead of stopping the parse.
+///
+/// Skipping a line shifts every later reading into an earlier window, so check `skipped` before trusting a verdict.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::parse_readings_lenient;
+/// let parsed = parse_readings_lenient::<u128>("35\nERR\n20\n-5\n");
+/// assert_eq!(parsed.readings, vec![35, 20]);
+/// assert_eq!(parsed.skipped.len(), 2);
+/// assert_eq!(parsed.skipped[1].line, 4);
+/// ```
+pub fn parse_readings_lenient<T: Reading>(text: &str) -> LenientReadings<T> {
+    let mut parsed = LenientReadings {
+        readings: vec![],
+        skipped: vec![],
+    };
+    for (line, content) in text.lines().enumerate() {
+        match parse_line(line + 1, content) {
+            Ok(Some(reading)) => parsed.readings.push(reading),
+            Ok(None) => {}
+            Err(error) => parsed.skipped.push(error),
+        }
+    }
+    parsed
+}
+
+///`None` for a blank or comment line.
+fn parse_line<T: Reading>(line: usize, text: &str) -> Result<Option<T>, ParseError> {
+    let content = match text.find('#') {
+        Some(comment) => &text[..comment],
+        None => text,
+    };
+    let reading = content.trim();
+    if reading.is_empty() {
+        return Ok(None);
+    }
+    let start = content.len() - content.trim_start().len();
+    //Offsets are bytes into `reading`, columns are characters into the line.
+    parse_reading(reading)
+        .map(Some)
+        .map_err(|(offset, kind)| ParseError {
+            line,
+            column: text[..start + offset].chars().count() + 1,
+            kind,
+        })
+}
+
+///One trimmed, non-empty reading. Errors carry the byte offset of the offending character into `reading`.
+pub(crate) fn parse_reading<T: Reading>(reading: &str) -> Result<T, (usize, ParseErrorKind)> {
+    let unsigned = reading.strip_prefix(['+', '-']).unwrap_or(reading);
+    let sign = &reading[..reading.len() - unsigned.len()];
+    let (radix, digits) = match unsigned
+        .strip_prefix("0x")
+        .or_else(|| unsigned.strip_prefix("0X"))
+    {
+        Some(digits) => (16, digits),
+        None => (10, unsigned),
+    };
+    let digits_start = reading.len() - digits.len();
+    let mut cleaned = String::from(sign);
+    for (offset, c) in digits.char_indices() {
+        match c {
+            '_' => {}
+            c if c.is_digit(radix) => cleaned.push(c),
+            _ => return Err((digits_start + offset, ParseErrorKind::InvalidDigit)),
+        }
+    }
+    if cleaned.len() == sign.len() {
+        return Err((0, ParseErrorKind::NoDigits));
+    }
+    T::from_str_radix(&cleaned, radix).map_err(|parse_error| match parse_error.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => (0, ParseErrorKind::Overflow),
+        //Only a sign the reading type cannot hold gets this far.
+        _ => (0, ParseErrorKind::InvalidDigit),
+    })
+}
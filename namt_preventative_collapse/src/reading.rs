@@ -0,0 +1,112 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::num::ParseIntError;
+
+/// An integer sensor reading the collapse checks can work on directly, without widening it to `u128` first.
+///
+/// Implemented for every primitive integer type, signed and unsigned. The checks only ever add or subtract
+/// readings through the checked methods, so no reading type overflows, whatever the window holds.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::collapse_check;
+/// let buffer: Vec<u16> = vec![1, 2, 3, 4];
+/// assert_eq!(true, collapse_check(&buffer, 3).is_ok());
+///
+/// let buffer: Vec<i32> = vec![-3, 5, -1, 2];
+/// assert_eq!(true, collapse_check(&buffer, 3).is_ok());
+/// ```
+pub trait Reading: Copy + Ord + Hash + Debug + Display {
+    /// The zero reading, which tells negative readings apart.
+    const ZERO: Self;
+
+    /// `self + other`, or `None` past the range of the type.
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// `self - other`, or `None` past the range of the type.
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    /// The reading as a float, for statistics over the window. Rounds readings past 2^53.
+    fn as_f64(self) -> f64;
+
+    /// Parses digits in `radix`, with an optional sign, as `from_str_radix` on the primitive type.
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError>;
+}
+
+macro_rules! impl_reading {
+    ($($reading:ty),*) => {
+        $(
+            impl Reading for $reading {
+                const ZERO: Self = 0;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$reading>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$reading>::checked_sub(self, other)
+                }
+
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+                    <$reading>::from_str_radix(src, radix)
+                }
+            }
+        )*
+    };
+}
+
+impl_reading!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+///`2 * value < target`, without overflowing. Twice a reading past the range of the type is past every target,
+/// on the side of its sign.
+pub(crate) fn double_is_below<T: Reading>(value: T, target: T) -> bool {
+    value
+        .checked_add(value)
+        .map_or(value < T::ZERO, |double| double < target)
+}
+
+///`2 * value > target`, without overflowing.
+pub(crate) fn double_is_above<T: Reading>(value: T, target: T) -> bool {
+    value
+        .checked_add(value)
+        .map_or(value > T::ZERO, |double| double > target)
+}
+
+///The readings `b` that let `a = element` reach `target`. `target - a` makes `a + b = target`, and `a - target`
+/// makes `a - b = target`. Unsigned readings only ever have one of them, which is `|target - a|`.
+/// Signed readings can have both, and the absolute value would miss a negative `b`.
+pub(crate) fn inverses<T: Reading>(target: T, element: T) -> impl Iterator<Item = T> {
+    [target.checked_sub(element), element.checked_sub(target)]
+        .into_iter()
+        .flatten()
+}
+
+///`terms[0] + terms[1] + ...` against `target`, for `terms` in ascending order, without overflowing.
+/// Adds the smallest term left while the total is at or above zero, and the largest otherwise, so the total can only
+/// leave the range of the type when every term left pushes it further the same way.
+pub(crate) fn sorted_sum_cmp<T: Reading>(terms: &[T], target: T) -> Ordering {
+    let (mut low, mut high) = (0, terms.len());
+    let mut total = T::ZERO;
+    while low < high {
+        let term = match total >= T::ZERO {
+            true => {
+                low += 1;
+                terms[low - 1]
+            }
+            false => {
+                high -= 1;
+                terms[high]
+            }
+        };
+        match total.checked_add(term) {
+            Some(sum) => total = sum,
+            None if term < T::ZERO => return Ordering::Less,
+            None => return Ordering::Greater,
+        }
+    }
+    total.cmp(&target)
+}
@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use crate::{
+    collapse_check_records, collapse_check_records_timed, collapse_scan_records,
+    collapse_scan_records_timed, collapse_witnesses, CollapseError, Reading, Record, Strategy,
+    Violation, Witness,
+};
+
+/// The outcome of checking a whole input, for dashboards and other tools that read results rather than people.
+///
+/// With the `serde` feature, every result type is `Serialize`, and a report serializes to one flat object: the
+/// [`Summary`] fields, then `anomalies` and, when present, `witnesses`. Anomalies are tagged by `kind`, such as
+/// `{"kind":"MinTooBig","index":14,"target":127,"min":95,"max":182}`, and a [`Violation`] adds its `record`,
+/// `timestamp` and `sensor`.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{Report, Strategy};
+/// let vector = vec![35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127];
+/// let report = Report::new(&vector, 5, Strategy::Resort, true);
+/// assert_eq!(report.summary.safe, false);
+/// assert_eq!(report.summary.readings, 15);
+/// assert_eq!(report.summary.max_reading, Some(182));
+/// assert_eq!(report.anomalies[0].index(), 14);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Report<A = CollapseError, T = u128> {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub summary: Summary<T>,
+    /// Every anomaly found, or only the first when the check stopped there.
+    pub anomalies: Vec<A>,
+    /// The pair that validated each checked reading, when asked for with `with_witnesses`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub witnesses: Vec<Witness<T>>,
+}
+
+/// What was checked and how, and the verdict.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Summary<T = u128> {
+    /// Number of readings in the input.
+    pub readings: usize,
+    /// Number of distinct sensors, for CSV records.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub sensors: Option<usize>,
+    /// Smallest and largest reading in the input. `None` when it is empty.
+    pub min_reading: Option<T>,
+    pub max_reading: Option<T>,
+    /// Readings each reading was checked against. `None` for a window defined by duration.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub period: Option<usize>,
+    /// Duration of the window in seconds, instead of `period`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub window_seconds: Option<f64>,
+    /// The implementation that ran. `None` for a window defined by duration, which has only one.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub strategy: Option<Strategy>,
+    /// `true` when no anomaly was found.
+    pub safe: bool,
+}
+
+impl<T: Reading> Summary<T> {
+    fn new(values: impl Iterator<Item = T> + Clone, readings: usize) -> Self {
+        Summary {
+            readings,
+            sensors: None,
+            min_reading: values.clone().min(),
+            max_reading: values.max(),
+            period: None,
+            window_seconds: None,
+            strategy: None,
+            safe: true,
+        }
+    }
+}
+
+impl<T: Reading> Report<CollapseError<T>, T> {
+    /// Runs `strategy` over `vector`, and reports every anomaly if `all`, or only the first.
+    /// ### Panics
+    /// If `period` is zero.
+    pub fn new(vector: &[T], period: usize, strategy: Strategy, all: bool) -> Self {
+        let anomalies = match all {
+            true => strategy.collapse_scan(vector, period),
+            false => strategy
+                .collapse_check(vector, period)
+                .err()
+                .into_iter()
+                .collect(),
+        };
+        let mut summary = Summary::new(vector.iter().copied(), vector.len());
+        summary.period = Some(period);
+        summary.strategy = Some(strategy);
+        summary.safe = anomalies.is_empty();
+        Report {
+            summary,
+            anomalies,
+            witnesses: vec![],
+        }
+    }
+
+    /// Adds the pair that validated each checked reading of `vector`, which must be the input of the report.
+    /// Left empty unless the report is safe, since witnesses stop at the first anomaly.
+    pub fn with_witnesses(mut self, vector: &[T]) -> Self {
+        if let (true, Some(period)) = (self.summary.safe, self.summary.period) {
+            self.witnesses = collapse_witnesses(vector, period).unwrap_or_default();
+        }
+        self
+    }
+}
+
+impl<T: Reading> Report<Violation<T>, T> {
+    /// Runs `strategy` over each sensor of `records`, and reports every violation if `all`, or only the first.
+    /// ### Panics
+    /// If `period` is zero.
+    pub fn records(records: &[Record<T>], period: usize, strategy: Strategy, all: bool) -> Self {
+        let anomalies = match all {
+            true => collapse_scan_records(records, period, strategy),
+            false => collapse_check_records(records, period, strategy)
+                .err()
+                .into_iter()
+                .collect(),
+        };
+        let mut summary = records_summary(records);
+        summary.period = Some(period);
+        summary.strategy = Some(strategy);
+        summary.safe = anomalies.is_empty();
+        Report {
+            summary,
+            anomalies,
+            witnesses: vec![],
+        }
+    }
+
+    /// `records`, with a window defined by duration.
+    /// ### Panics
+    /// If `window` is zero.
+    pub fn records_timed(records: &[Record<T>], window: Duration, all: bool) -> Self {
+        let anomalies = match all {
+            true => collapse_scan_records_timed(records, window),
+            false => collapse_check_records_timed(records, window)
+                .err()
+                .into_iter()
+                .collect(),
+        };
+        let mut summary = records_summary(records);
+        summary.window_seconds = Some(window.as_secs_f64());
+        summary.safe = anomalies.is_empty();
+        Report {
+            summary,
+            anomalies,
+            witnesses: vec![],
+        }
+    }
+}
+
+fn records_summary<T: Reading>(records: &[Record<T>]) -> Summary<T> {
+    let mut sensors: Vec<&str> = records
+        .iter()
+        .map(|record| record.sensor.as_str())
+        .collect();
+    sensors.sort_unstable();
+    sensors.dedup();
+    let mut summary = Summary::new(records.iter().map(|record| record.value), records.len());
+    summary.sensors = Some(sensors.len());
+    summary
+}
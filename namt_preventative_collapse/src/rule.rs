@@ -0,0 +1,129 @@
+use std::cmp::Ordering;
+
+use crate::incremental::sum_cmp;
+use crate::window_rule::early_warnings;
+use crate::{
+    collapse_check_with, collapse_scan_with, CollapseError, CollapseStrategy, IncrementalWindow,
+    Reading, ResortWindow,
+};
+
+/// Which pairs of window readings count as reaching a new reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PairRule {
+    /// What `collapse_check` has always done. A reading is safe when `|target - a|` is in the window for some `a`.
+    /// That accepts `a + a`, one slot used twice, and differences `a - b`, as well as genuine sums.
+    /// Signed readings are held to the same sums and differences, `target - a` or `a - target` in the window.
+    #[default]
+    Legacy,
+    /// Only `a + b == target` counts, and `a` and `b` must come from different positions in the window.
+    /// With `distinct_values`, `a != b` as well, so two equal readings never validate their double.
+    Strict { distinct_values: bool },
+}
+
+impl PairRule {
+    /// Runs the collapse check under this rule.
+    /// ### Arguments
+    /// * `vector:` A vector
+    /// * `period:` Determines the range `i..i+k`, where i+k is not included.
+    /// ### Example
+    /// ```
+    /// use namt_preventative_collapse::PairRule;
+    /// //4 is twice 2, and 5 - 1, but no two different readings sum to it.
+    /// let vector = vec![5, 2, 1, 4];
+    /// assert_eq!(true, PairRule::Legacy.collapse_check(&vector, 3).is_ok());
+    /// assert_eq!(
+    ///     false,
+    ///     PairRule::Strict { distinct_values: false }.collapse_check(&vector, 3).is_ok()
+    /// );
+    /// ```
+    pub fn collapse_check<T: Reading>(
+        self,
+        vector: &[T],
+        period: usize,
+    ) -> Result<(), CollapseError<T>> {
+        match self {
+            PairRule::Legacy => collapse_check_with::<ResortWindow<T>>(vector, period),
+            PairRule::Strict {
+                distinct_values: false,
+            } => collapse_check_with::<StrictWindow<false, T>>(vector, period),
+            PairRule::Strict {
+                distinct_values: true,
+            } => collapse_check_with::<StrictWindow<true, T>>(vector, period),
+        }
+    }
+
+    /// Runs `collapse_scan` under this rule.
+    pub fn collapse_scan<T: Reading>(self, vector: &[T], period: usize) -> Vec<CollapseError<T>> {
+        match self {
+            PairRule::Legacy => collapse_scan_with::<ResortWindow<T>>(vector, period),
+            PairRule::Strict {
+                distinct_values: false,
+            } => collapse_scan_with::<StrictWindow<false, T>>(vector, period),
+            PairRule::Strict {
+                distinct_values: true,
+            } => collapse_scan_with::<StrictWindow<true, T>>(vector, period),
+        }
+    }
+}
+
+/// The strategy behind [`PairRule::Strict`]. Keeps the window sorted as it slides, like [`IncrementalWindow`],
+/// and only accepts `a + b == target` from two different positions. `DISTINCT_VALUES` also requires `a != b`.
+#[derive(Debug, Clone)]
+pub struct StrictWindow<const DISTINCT_VALUES: bool, T = u128>(IncrementalWindow<T>);
+
+impl<const DISTINCT_VALUES: bool, T: Reading> CollapseStrategy
+    for StrictWindow<DISTINCT_VALUES, T>
+{
+    type Reading = T;
+
+    fn from_preamble(preamble: &[T]) -> Self {
+        StrictWindow(IncrementalWindow::from_preamble(preamble))
+    }
+
+    fn check(&mut self, index: usize, target: T) -> Result<(), CollapseError<T>> {
+        pair_exists_strict(&self.0.sorted, index, target, DISTINCT_VALUES)
+    }
+
+    fn slide(&mut self, expired: T, incoming: T) {
+        self.0.slide(expired, incoming);
+    }
+}
+
+///Same three cases as `pair_exists`, but the normal warning only accepts sums of two different positions. O(n).
+pub(crate) fn pair_exists_strict<T: Reading>(
+    sorted: &[T],
+    index: usize,
+    target: T,
+    distinct_values: bool,
+) -> Result<(), CollapseError<T>> {
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    //Early warnings. They hold under any rule, a + b can never leave [2 * min, 2 * max].
+    early_warnings(index, target, min, max)?;
+    match strict_sum_exists(sorted, target, distinct_values) {
+        true => Ok(()),
+        false => Err(CollapseError::NoPairFound {
+            index,
+            target,
+            min,
+            max,
+        }),
+    }
+}
+
+///`sorted[i] + sorted[j] == target` for some `i < j`, and `sorted[i] != sorted[j]` if `distinct_values`.
+fn strict_sum_exists<T: Reading>(sorted: &[T], target: T, distinct_values: bool) -> bool {
+    if sorted.len() < 2 {
+        return false;
+    }
+    let (mut low, mut high) = (0, sorted.len() - 1);
+    while low < high {
+        match sum_cmp(sorted[low], sorted[high], target) {
+            //Equal ends mean everything between is equal too, so no pair of different values is left.
+            Ordering::Equal => return !distinct_values || sorted[low] != sorted[high],
+            Ordering::Less => low += 1,
+            Ordering::Greater => high -= 1,
+        }
+    }
+    false
+}
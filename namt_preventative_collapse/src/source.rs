@@ -0,0 +1,110 @@
+#[cfg(feature = "mmap")]
+use crate::ParseError;
+use crate::{CollapseError, CollapseMonitor, Reading, ResortWindow};
+
+/// `collapse_check` over any source of readings, such as a ring buffer, a channel or a parser, without collecting
+/// them into a slice first. Only the last `period` readings are kept, so memory does not grow with the input.
+/// ### Arguments
+/// * `readings:` The series, in order.
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::collapse_check_iter;
+/// use std::collections::VecDeque;
+/// let buffer: VecDeque<u32> = VecDeque::from(vec![1, 2, 3, 4]);
+/// assert_eq!(collapse_check_iter(buffer.iter().copied(), 3), Ok(()));
+/// assert!(collapse_check_iter([1, 1, 1, 4], 3).is_err());
+/// ```
+/// ### Panics
+/// If `period` is zero.
+pub fn collapse_check_iter<T: Reading>(
+    readings: impl IntoIterator<Item = T>,
+    period: usize,
+) -> Result<(), CollapseError<T>> {
+    let mut monitor = CollapseMonitor::<ResortWindow<T>>::with_strategy(period);
+    for reading in readings {
+        monitor.push(reading)?;
+    }
+    Ok(())
+}
+
+/// `collapse_scan` over any source of readings, reporting each anomaly as soon as the reading behind it is checked.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_scan_iter, CollapseError};
+/// let anomalies = collapse_scan_iter([1, 1, 1, 4, 2, 9], 3);
+/// assert_eq!(anomalies.map(|error| error.index()).collect::<Vec<_>>(), vec![3, 5]);
+/// ```
+/// ### Panics
+/// If `period` is zero.
+pub fn collapse_scan_iter<T: Reading, I: IntoIterator<Item = T>>(
+    readings: I,
+    period: usize,
+) -> impl Iterator<Item = CollapseError<T>> {
+    let mut monitor = CollapseMonitor::<ResortWindow<T>>::with_strategy(period);
+    readings
+        .into_iter()
+        .filter_map(move |reading| monitor.push(reading).err())
+}
+
+/// A log file mapped into memory, for multi-gigabyte logs that should be checked without reading them into a
+/// `String` or their readings into a `Vec`. The operating system pages the file in as the check walks it.
+/// Requires the `mmap` feature.
+/// ### Example
+/// ```no_run
+/// use namt_preventative_collapse::MappedReadings;
+/// let log = MappedReadings::open("mine.log").unwrap();
+/// match log.collapse_check::<u64>(100) {
+///     Ok(Ok(())) => println!("Safe."),
+///     Ok(Err(error)) => println!("{}", error),
+///     Err(error) => println!("Bad input: {}", error),
+/// }
+/// ```
+#[cfg(feature = "mmap")]
+#[derive(Debug)]
+pub struct MappedReadings {
+    map: memmap2::Mmap,
+}
+
+#[cfg(feature = "mmap")]
+impl MappedReadings {
+    /// Maps the file at `path`.
+    /// ### Errors
+    /// If the file cannot be opened or mapped, and `InvalidData` if it is not UTF-8.
+    pub fn open(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: The map is only read. Like any file map, it assumes the file is not truncated or rewritten while
+        // it is open, which holds for logs that are only appended to.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        std::str::from_utf8(&map)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+        Ok(MappedReadings { map })
+    }
+
+    /// The whole file.
+    pub fn text(&self) -> &str {
+        // SAFETY: Validated as UTF-8 in `open`.
+        unsafe { std::str::from_utf8_unchecked(&self.map) }
+    }
+
+    /// The readings of the file, parsed as the iterator is advanced, as `parse_readings_iter`.
+    pub fn readings<T: Reading>(&self) -> impl Iterator<Item = Result<T, ParseError>> + '_ {
+        crate::parse_readings_iter(self.text())
+    }
+
+    /// `collapse_check` over the file. Stops at the first malformed line, and reports it as the outer error.
+    /// ### Panics
+    /// If `period` is zero.
+    pub fn collapse_check<T: Reading>(
+        &self,
+        period: usize,
+    ) -> Result<Result<(), CollapseError<T>>, ParseError> {
+        let mut monitor = CollapseMonitor::<ResortWindow<T>>::with_strategy(period);
+        for reading in self.readings() {
+            if let Err(error) = monitor.push(reading?) {
+                return Ok(Err(error));
+            }
+        }
+        Ok(Ok(()))
+    }
+}
@@ -0,0 +1,195 @@
+use crate::observer::{check_observed, scan_observed};
+use crate::{pair_exists, CollapseError, HashedWindow, IncrementalWindow, Reading};
+
+/// A way of searching the window for a pair that reaches each new reading.
+///
+/// The window bookkeeping lives in `collapse_check_with`, `collapse_scan_with` and `CollapseMonitor`:
+/// they hand the strategy the first `period` readings, ask it to check each reading after that,
+/// then tell it which reading left the window and which one entered.
+/// An implementation only decides how to store the window and how to search it, and which [`Reading`] type it holds.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check_with, CollapseError, CollapseStrategy};
+///
+/// ///Brute force. Checks every pair of readings.
+/// struct EveryPair(Vec<u128>);
+///
+/// impl CollapseStrategy for EveryPair {
+///     type Reading = u128;
+///     fn from_preamble(preamble: &[u128]) -> Self {
+///         EveryPair(preamble.to_vec())
+///     }
+///     fn check(&mut self, index: usize, target: u128) -> Result<(), CollapseError> {
+///         let (min, max) = (*self.0.iter().min().unwrap(), *self.0.iter().max().unwrap());
+///         match self.0.iter().any(|a| self.0.iter().any(|b| a + b == target)) {
+///             true => Ok(()),
+///             false => Err(CollapseError::NoPairFound { index, target, min, max }),
+///         }
+///     }
+///     fn slide(&mut self, _expired: u128, incoming: u128) {
+///         self.0.remove(0);
+///         self.0.push(incoming);
+///     }
+/// }
+///
+/// assert!(collapse_check_with::<EveryPair>(&[1, 2, 3, 4], 3).is_ok());
+/// assert!(collapse_check_with::<EveryPair>(&[1, 2, 3, 9], 3).is_err());
+/// ```
+pub trait CollapseStrategy {
+    /// The type of the readings in the window.
+    type Reading: Reading;
+
+    /// Builds the window from the first `period` readings, oldest first.
+    fn from_preamble(preamble: &[Self::Reading]) -> Self
+    where
+        Self: Sized;
+
+    /// Checks `target`, the reading at `index` in the input, against the current window.
+    fn check(
+        &mut self,
+        index: usize,
+        target: Self::Reading,
+    ) -> Result<(), CollapseError<Self::Reading>>;
+
+    /// Slides the window forward one reading. `expired` is the oldest reading, `incoming` the one just checked.
+    fn slide(&mut self, expired: Self::Reading, incoming: Self::Reading);
+}
+
+/// Checks for imminent mine collapse of mining operation, with the pair search chosen at compile time.
+/// ### Arguments
+/// * `vector:` A vector
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check_with, HashedWindow, ResortWindow};
+/// let vector = vec![1,1,1,4];
+/// assert_eq!(
+///     collapse_check_with::<HashedWindow>(&vector, 3),
+///     collapse_check_with::<ResortWindow>(&vector, 3)
+/// );
+/// ```
+pub fn collapse_check_with<S: CollapseStrategy>(
+    vector: &[S::Reading],
+    period: usize,
+) -> Result<(), CollapseError<S::Reading>> {
+    check_observed::<S, ()>(vector, period, &mut ())
+}
+
+/// `collapse_scan`, with the pair search chosen at compile time.
+/// Failing readings still enter the window, so every index is judged against exactly the `period` readings before it.
+pub fn collapse_scan_with<S: CollapseStrategy>(
+    vector: &[S::Reading],
+    period: usize,
+) -> Vec<CollapseError<S::Reading>> {
+    scan_observed::<S, ()>(vector, period, &mut ())
+}
+
+/// The window as `(reading, position)` tuples, where position 0 is the oldest reading.
+/// Shared by every strategy that sorts the whole window before searching it.
+#[derive(Debug, Clone)]
+pub(crate) struct PositionedWindow<T = u128> {
+    values: Vec<(T, usize)>,
+}
+
+impl<T: Reading> PositionedWindow<T> {
+    pub(crate) fn from_preamble(preamble: &[T]) -> Self {
+        PositionedWindow {
+            values: preamble.iter().copied().zip(0..preamble.len()).collect(), //O(n)
+        }
+    }
+
+    ///Sorts the window by reading and returns it.
+    pub(crate) fn sorted(&mut self) -> &[(T, usize)] {
+        self.values.sort_by_key(|(entry, _)| *entry); //O(n*log(n)) first time. O(n) every time after.
+
+        '_ensure_sorting: for pair in self.values.windows(2) {
+            assert!(pair[0].0 <= pair[1].0);
+            // Scaffolding. Do not remove. Any logic after this assertion block relies on the assumption the values are sorted.
+            // Also avoiding nightly build of Rust, .is_sorted();
+        } // O(n)
+        &self.values
+    }
+
+    pub(crate) fn slide(&mut self, incoming: T) {
+        let period = self.values.len();
+        //Decrement indexes, and insert (incoming, period -1) in the place of (some_element, 0).
+        for entry in self.values.iter_mut() {
+            if entry.1 != 0 {
+                entry.1 -= 1;
+                continue;
+            }
+            *entry = (incoming, period - 1);
+        } //O(n)
+          //remove zero index
+        for entry in self.values.iter() {
+            assert!(entry.1 < period, "Logic Error: Indexing operations.");
+        }
+    }
+}
+
+/// The strategy behind `collapse_check`. Re-sorts the window every step, then binary searches for each inverse.
+#[derive(Debug, Clone)]
+pub struct ResortWindow<T = u128>(PositionedWindow<T>);
+
+impl<T: Reading> CollapseStrategy for ResortWindow<T> {
+    type Reading = T;
+
+    fn from_preamble(preamble: &[T]) -> Self {
+        ResortWindow(PositionedWindow::from_preamble(preamble))
+    }
+
+    fn check(&mut self, index: usize, target: T) -> Result<(), CollapseError<T>> {
+        pair_exists(self.0.sorted(), index, target)
+    }
+
+    fn slide(&mut self, _expired: T, incoming: T) {
+        self.0.slide(incoming);
+    }
+}
+
+/// The production implementations of the collapse check, for choosing one at run time. They all reach the same verdicts.
+/// To choose at compile time, or to run one of the `bench_iterations` experiments, use `collapse_check_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Strategy {
+    /// [`ResortWindow`], used by `collapse_check`. Re-sorts the window every step.
+    #[default]
+    Resort,
+    /// [`HashedWindow`], used by `collapse_check_hashed`. Keeps the window as reading counts.
+    Hashed,
+    /// [`IncrementalWindow`], used by `collapse_check_incremental`. Keeps the window sorted as it slides.
+    Incremental,
+}
+
+impl Strategy {
+    /// Runs the implementation this strategy names.
+    /// ### Example
+    /// ```
+    /// use namt_preventative_collapse::Strategy;
+    /// let vector = vec![1,1,1,4];
+    /// for strategy in [Strategy::Resort, Strategy::Hashed, Strategy::Incremental] {
+    ///     assert_eq!(false, strategy.collapse_check(&vector, 3).is_ok());
+    /// }
+    /// ```
+    pub fn collapse_check<T: Reading>(
+        self,
+        vector: &[T],
+        period: usize,
+    ) -> Result<(), CollapseError<T>> {
+        match self {
+            Strategy::Resort => collapse_check_with::<ResortWindow<T>>(vector, period),
+            Strategy::Hashed => collapse_check_with::<HashedWindow<T>>(vector, period),
+            Strategy::Incremental => collapse_check_with::<IncrementalWindow<T>>(vector, period),
+        }
+    }
+
+    /// Runs `collapse_scan` with the implementation this strategy names.
+    pub fn collapse_scan<T: Reading>(self, vector: &[T], period: usize) -> Vec<CollapseError<T>> {
+        match self {
+            Strategy::Resort => collapse_scan_with::<ResortWindow<T>>(vector, period),
+            Strategy::Hashed => collapse_scan_with::<HashedWindow<T>>(vector, period),
+            Strategy::Incremental => collapse_scan_with::<IncrementalWindow<T>>(vector, period),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::incremental::pair_exists_two_pointer;
+use crate::{CollapseError, Reading, Verdict};
+
+/// Streaming collapse check over a window defined by duration instead of `period`, for loggers that sample at
+/// irregular intervals.
+///
+/// Each reading is checked against every reading logged in the `window` before its timestamp, expiring by time
+/// rather than by position. A reading logged exactly `window` earlier is still in the window, so when sampling is
+/// uniform, every `window / period` apart, the verdicts are those of `CollapseMonitor::new(period)`. The monitor
+/// warms up until the window spans a whole `window` of readings.
+///
+/// Timestamps are a [`Duration`] since any epoch, as long as every reading uses the same one, such as the ones
+/// [`parse_timestamp`] returns. They must not go backwards. The window is kept sorted as it slides, like
+/// [`IncrementalWindow`](crate::IncrementalWindow).
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{CollapseError, TimedMonitor, Verdict};
+/// use std::time::Duration;
+/// let minutes = |m: u64| Duration::from_secs(m * 60);
+/// let mut monitor = TimedMonitor::new(minutes(10));
+/// assert_eq!(monitor.push(minutes(0), 20), Ok(Verdict::Warming));
+/// assert_eq!(monitor.push(minutes(4), 15), Ok(Verdict::Warming));
+/// assert_eq!(monitor.push(minutes(10), 35), Ok(Verdict::Safe));
+/// //20 left the window after minute 10, so 35 - 15 = 20 is no longer in it.
+/// assert!(matches!(monitor.push(minutes(14), 35), Err(CollapseError::NoPairFound { .. })));
+/// //Nothing was logged in the 10 minutes before minute 40.
+/// assert!(matches!(monitor.push(minutes(40), 3), Err(CollapseError::EmptyWindow { index: 4, .. })));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimedMonitor<T = u128> {
+    window: Duration,
+    readings: VecDeque<(Duration, T)>,
+    sorted: Vec<T>,
+    first: Option<Duration>,
+    latest: Option<Duration>,
+    index: usize,
+}
+
+impl<T: Reading> TimedMonitor<T> {
+    /// Creates a monitor that checks each reading against the readings logged in the `window` before it.
+    /// ### Panics
+    /// If `window` is zero. There is no pair in an empty window.
+    pub fn new(window: Duration) -> Self {
+        assert!(
+            !window.is_zero(),
+            "A window of zero leaves nothing to check against."
+        );
+        TimedMonitor {
+            window,
+            readings: VecDeque::new(),
+            sorted: vec![],
+            first: None,
+            latest: None,
+            index: 0,
+        }
+    }
+
+    /// Expires the readings logged more than `window` before `timestamp`, checks `reading` against the rest, then
+    /// adds it to the window.
+    /// The index in any error counts every reading pushed so far, starting at zero.
+    pub fn push(&mut self, timestamp: Duration, reading: T) -> Result<Verdict, CollapseError<T>> {
+        let index = self.index;
+        self.index += 1;
+        if self.latest.is_some_and(|latest| timestamp < latest) {
+            return Err(CollapseError::BadTimestamp {
+                index,
+                target: reading,
+            });
+        }
+        self.latest = Some(timestamp);
+
+        //None while the window reaches back before the epoch.
+        let start = timestamp.checked_sub(self.window);
+        while let Some(&(time, expired)) = self.readings.front() {
+            if start.is_none_or(|start| time >= start) {
+                break;
+            }
+            self.readings.pop_front();
+            let position = self.sorted.binary_search(&expired).unwrap();
+            self.sorted.remove(position);
+        } //O(n) per expired reading
+        let first = *self.first.get_or_insert(timestamp);
+
+        let result = match start {
+            Some(start) if start >= first => match self.sorted.is_empty() {
+                true => Err(CollapseError::EmptyWindow {
+                    index,
+                    target: reading,
+                }),
+                false => {
+                    pair_exists_two_pointer(&self.sorted, index, reading).map(|_| Verdict::Safe)
+                }
+            },
+            _ => Ok(Verdict::Warming),
+        };
+        self.readings.push_back((timestamp, reading));
+        let position = self.sorted.partition_point(|&value| value < reading);
+        self.sorted.insert(position, reading); //O(n)
+        result
+    }
+
+    ///Counts a reading whose timestamp is not a time, without checking it.
+    pub(crate) fn skip(&mut self, reading: T) -> CollapseError<T> {
+        self.index += 1;
+        CollapseError::BadTimestamp {
+            index: self.index - 1,
+            target: reading,
+        }
+    }
+
+    /// Duration of the window each new reading is checked against.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Number of readings pushed so far, which is also the index of the next one.
+    pub fn len(&self) -> usize {
+        self.index
+    }
+
+    /// `true` until the first reading is pushed.
+    pub fn is_empty(&self) -> bool {
+        self.index == 0
+    }
+}
+
+/// Checks for imminent mine collapse of mining operation, with a window defined by duration.
+/// ### Arguments
+/// * `readings:` Timestamped readings, in the order they were logged.
+/// * `window:` Each reading is checked against the readings logged in this duration before it.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check, collapse_check_timed};
+/// use std::time::Duration;
+/// let vector = vec![35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127];
+/// //One reading a second, so a 5 second window holds 5 readings.
+/// let timed: Vec<(Duration, u128)> =
+///     vector.iter().enumerate().map(|(c, &x)| (Duration::from_secs(c as u64), x)).collect();
+/// assert_eq!(collapse_check_timed(&timed, Duration::from_secs(5)), collapse_check(&vector, 5));
+/// ```
+/// ### Panics
+/// If `window` is zero.
+pub fn collapse_check_timed<T: Reading>(
+    readings: &[(Duration, T)],
+    window: Duration,
+) -> Result<(), CollapseError<T>> {
+    let mut monitor = TimedMonitor::new(window);
+    for &(timestamp, reading) in readings {
+        monitor.push(timestamp, reading)?;
+    }
+    Ok(())
+}
+
+/// `collapse_scan`, with a window defined by duration.
+/// ### Panics
+/// If `window` is zero.
+pub fn collapse_scan_timed<T: Reading>(
+    readings: &[(Duration, T)],
+    window: Duration,
+) -> Vec<CollapseError<T>> {
+    let mut monitor = TimedMonitor::new(window);
+    readings
+        .iter()
+        .filter_map(|&(timestamp, reading)| monitor.push(timestamp, reading).err())
+        .collect()
+}
+
+/// Parses a logger timestamp into the time since the Unix epoch.
+///
+/// Accepts Unix seconds, such as `1709272800` or `1709272800.25`, and RFC 3339 dates and times, such as
+/// `2024-03-01T06:00:00Z`, `2024-03-01 06:00:00.250` or `2024-03-01T08:00:00+02:00`. A time without an offset is
+/// taken as UTC. `None` for anything else, or a time before the epoch.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::parse_timestamp;
+/// use std::time::Duration;
+/// let time = parse_timestamp("2024-03-01T06:00:00Z");
+/// assert_eq!(time, Some(Duration::from_secs(1709272800)));
+/// assert_eq!(parse_timestamp("2024-03-01T08:00:00+02:00"), time);
+/// assert_eq!(parse_timestamp("1709272800.5"), Some(Duration::from_millis(1709272800500)));
+/// assert_eq!(parse_timestamp("2024-02-30T06:00:00Z"), None);
+/// ```
+pub fn parse_timestamp(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    if text
+        .bytes()
+        .all(|byte| byte.is_ascii_digit() || byte == b'.')
+    {
+        return seconds(text);
+    }
+    let (date, time) = text.split_once(['T', 't', ' '])?;
+    let (year, month, day) = match date.split('-').collect::<Vec<_>>()[..] {
+        [year, month, day] if year.len() == 4 && month.len() == 2 && day.len() == 2 => {
+            (year.parse::<i64>().ok()?, number(month)?, number(day)?)
+        }
+        _ => return None,
+    };
+    let (time, offset) = match time.find(['Z', 'z', '+', '-']) {
+        Some(position) => (&time[..position], offset(&time[position..])?),
+        None => (time, 0),
+    };
+    let (hour, minute, second) = match time.splitn(3, ':').collect::<Vec<_>>()[..] {
+        [hour, minute, second] if second.len() >= 2 => (number(hour)?, number(minute)?, second),
+        _ => return None,
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = [
+        31,
+        if leap { 29 } else { 28 },
+        31,
+        30,
+        31,
+        30,
+        31,
+        31,
+        30,
+        31,
+        30,
+        31,
+    ];
+    if !(1..=12).contains(&month) || !(1..=days_in_month[month as usize - 1]).contains(&day) {
+        return None;
+    }
+    if hour > 23 || minute > 59 {
+        return None;
+    }
+    let second = seconds(second).filter(|second| second.as_secs() < 60)?;
+    let minutes = days_from_civil(year, month, day) * 1440 + hour * 60 + minute - offset;
+    Some(Duration::from_secs(u64::try_from(minutes).ok()? * 60) + second)
+}
+
+///Two ASCII digits.
+fn number(text: &str) -> Option<i64> {
+    match text.len() == 2 && text.bytes().all(|byte| byte.is_ascii_digit()) {
+        true => text.parse().ok(),
+        false => None,
+    }
+}
+
+///Whole seconds with an optional fraction, such as `05` or `1709272800.25`.
+fn seconds(text: &str) -> Option<Duration> {
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if whole.is_empty()
+        || !whole.bytes().all(|byte| byte.is_ascii_digit())
+        || !fraction.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        return None;
+    }
+    //Nanoseconds, so digits past the ninth are dropped.
+    let nanos = fraction
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(9)
+        .fold(0, |nanos, digit| nanos * 10 + u32::from(digit - b'0'));
+    Some(Duration::new(whole.parse().ok()?, nanos))
+}
+
+///Minutes east of UTC, from `Z`, `+HH:MM` or `-HH:MM`.
+fn offset(text: &str) -> Option<i64> {
+    if text.eq_ignore_ascii_case("z") {
+        return Some(0);
+    }
+    let (hours, minutes) = text[1..].split_once(':')?;
+    let (hours, minutes) = (number(hours)?, number(minutes)?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    match &text[..1] {
+        "+" => Some(hours * 60 + minutes),
+        _ => Some(-(hours * 60 + minutes)),
+    }
+}
+
+///Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
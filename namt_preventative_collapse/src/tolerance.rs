@@ -0,0 +1,175 @@
+use crate::CollapseError;
+
+/// How far a pair may land from a floating point reading and still reach it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance {
+    /// The pair may land up to this far from the reading, in the units of the readings.
+    Absolute(f64),
+    /// The pair may land up to this fraction of the reading's magnitude from it.
+    Relative(f64),
+}
+
+impl Tolerance {
+    ///How far from `target` the pair may land.
+    fn margin(self, target: f64) -> f64 {
+        match self {
+            Tolerance::Absolute(epsilon) => epsilon,
+            Tolerance::Relative(epsilon) => epsilon * target.abs(),
+        }
+    }
+
+    fn epsilon(self) -> f64 {
+        match self {
+            Tolerance::Absolute(epsilon) | Tolerance::Relative(epsilon) => epsilon,
+        }
+    }
+}
+
+/// Checks for imminent mine collapse of mining operation, on floating point readings with measurement noise.
+///
+/// Uses the same rule as `collapse_check`, but a sum `a + b` or difference `a - b` reaches a reading when it lands
+/// within `tolerance` of it, and the early warnings only fire when twice the window maximum or minimum misses
+/// the reading by more than `tolerance`. With `Tolerance::Absolute(0.0)`, on whole numbers small enough for `f64`
+/// to hold exactly, the verdicts are exactly those of `collapse_check`.
+///
+/// The window is kept sorted as it slides, like `collapse_check_incremental`, and searched with two pointers,
+/// so each reading costs O(period). A NaN or infinite reading is reported as [`CollapseError::NotFinite`]
+/// when the check reaches it.
+/// ### Arguments
+/// * `vector:` A vector
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// * `tolerance:` How far a pair may land from each reading.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check_tolerant, CollapseError, Tolerance};
+/// let loads = vec![1.02, 1.97, 3.01, 4.0];
+/// assert_eq!(true, collapse_check_tolerant(&loads, 3, Tolerance::Absolute(0.05)).is_ok());
+/// assert_eq!(false, collapse_check_tolerant(&loads, 3, Tolerance::Absolute(0.001)).is_ok());
+///
+/// let loads = vec![1.0, 2.0, f64::NAN, 4.0];
+/// let result = collapse_check_tolerant(&loads, 3, Tolerance::Relative(0.01));
+/// assert!(matches!(result, Err(CollapseError::NotFinite { index: 2, .. })));
+/// ```
+/// ### Panics
+/// If the tolerance is negative or not finite.
+pub fn collapse_check_tolerant(
+    vector: &[f64],
+    period: usize,
+    tolerance: Tolerance,
+) -> Result<(), CollapseError<f64>> {
+    assert!(
+        tolerance.epsilon().is_finite() && tolerance.epsilon() >= 0.0,
+        "A tolerance must be a finite, non-negative number."
+    );
+    for (c, &reading) in vector.iter().enumerate().take(period) {
+        finite(c, reading)?;
+    } //O(n)
+    if vector.len() <= period {
+        return Ok(()); //Safe.
+    }
+    let mut sorted = vector[..period].to_vec();
+    sorted.sort_unstable_by(f64::total_cmp); //O(n*log(n)) once.
+    for (c, &target) in vector.iter().enumerate().skip(period) {
+        finite(c, target)?;
+        pair_exists_tolerant(&sorted, c, target, tolerance)?;
+
+        //O(n) for the shift, O(log(n)) for the search.
+        let expired = vector[c - period];
+        let position = sorted.partition_point(|entry| *entry < expired);
+        sorted.remove(position);
+        let position = sorted.partition_point(|entry| *entry < target);
+        sorted.insert(position, target);
+    }
+    Ok(())
+}
+
+///
+/// Checks for existence of `a` and `b` in `sorted` such that `a + b` or `a - b` is within `tolerance` of `target`,
+/// and returns a Result. Assumes `sorted` holds finite readings in ascending order, otherwise the result is meaningless.
+/// `index` is the position of `target` in the original input, and is only used to report errors.
+///
+pub fn pair_exists_tolerant(
+    sorted: &[f64],
+    index: usize,
+    target: f64,
+    tolerance: Tolerance,
+) -> Result<(), CollapseError<f64>> {
+    finite(index, target)?;
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    let margin = tolerance.margin(target);
+    let (low, high) = (target - margin, target + margin);
+    match target {
+        //Early warnings
+        target if 2.0 * max < low => Err(CollapseError::MaxTooSmall {
+            index,
+            target,
+            min,
+            max,
+        }),
+        target if 2.0 * min > high => Err(CollapseError::MinTooBig {
+            index,
+            target,
+            min,
+            max,
+        }),
+        //a - b lands in [low, high] when the gap b - a, for b >= a, lands in [-high, -low].
+        _ if sum_within(sorted, low, high)
+            || gap_within(sorted, low.max(0.0), high)
+            || gap_within(sorted, (-high).max(0.0), -low) =>
+        {
+            Ok(())
+        }
+        _ => Err(CollapseError::NoPairFound {
+            index,
+            target,
+            min,
+            max,
+        }),
+    }
+}
+
+fn finite(index: usize, reading: f64) -> Result<(), CollapseError<f64>> {
+    match reading.is_finite() {
+        true => Ok(()),
+        false => Err(CollapseError::NotFinite {
+            index,
+            target: reading,
+        }),
+    }
+}
+
+///`sorted[i] + sorted[j]` in `[low, high]` for some `i <= j`. The same slot may be used twice.
+fn sum_within(sorted: &[f64], low: f64, high: f64) -> bool {
+    let (mut i, mut j) = (0, sorted.len() - 1);
+    while i <= j {
+        let sum = sorted[i] + sorted[j];
+        match sum {
+            sum if sum < low => i += 1,
+            sum if sum > high && j == 0 => break,
+            sum if sum > high => j -= 1,
+            _ => return true,
+        }
+    }
+    false
+}
+
+///`sorted[j] - sorted[i]` in `[low, high]` for some `i <= j`. An empty range never matches.
+fn gap_within(sorted: &[f64], low: f64, high: f64) -> bool {
+    if low > high {
+        return false;
+    }
+    let (mut i, mut j) = (0, 0);
+    while j < sorted.len() {
+        let gap = sorted[j] - sorted[i];
+        match gap {
+            gap if gap < low => j += 1,
+            gap if gap > high => {
+                i += 1;
+                j = j.max(i);
+            }
+            _ => return true,
+        }
+    }
+    false
+}
@@ -0,0 +1,265 @@
+use crate::incremental::pair_exists_two_pointer;
+use crate::ksum::ksum_check;
+use crate::reading::{double_is_above, double_is_below};
+use crate::rule::pair_exists_strict;
+use crate::{CollapseError, CollapseStrategy, IncrementalWindow, KSum, PairRule, Reading};
+
+/// A condition every reading must meet against the `period` readings before it.
+///
+/// `collapse_check_rule` and `collapse_scan_rule` keep the window sorted as it slides and hand it to the rule,
+/// so a mine profile only decides what a safe reading looks like. Each rule reports its own [`CollapseError`] variant.
+/// Rules compose: a tuple of rules, or a `Vec<Box<dyn WindowRule<T>>>` built at run time, checks each in order and
+/// reports the first that fails.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check_rule, CollapseError, MaxBound, WithinDeviations};
+/// let vector = vec![10, 12, 11, 13, 25];
+/// //25 is within twice the maximum, but far from the mean.
+/// assert!(collapse_check_rule(&vector, 4, &MaxBound).is_ok());
+/// let rule = (MaxBound, WithinDeviations(3.0));
+/// let result = collapse_check_rule(&vector, 4, &rule);
+/// assert!(matches!(result, Err(CollapseError::Outlier { index: 4, .. })));
+/// ```
+pub trait WindowRule<T: Reading = u128> {
+    /// Checks `target`, the reading at `index` in the input, against the window, sorted in ascending order.
+    fn check(&self, sorted: &[T], index: usize, target: T) -> Result<(), CollapseError<T>>;
+}
+
+/// Early warning. Twice the window maximum must reach the reading, otherwise [`CollapseError::MaxTooSmall`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MaxBound;
+
+impl MaxBound {
+    pub(crate) fn check_bounds<T: Reading>(
+        index: usize,
+        target: T,
+        min: T,
+        max: T,
+    ) -> Result<(), CollapseError<T>> {
+        match double_is_below(max, target) {
+            true => Err(CollapseError::MaxTooSmall {
+                index,
+                target,
+                min,
+                max,
+            }),
+            false => Ok(()),
+        }
+    }
+}
+
+impl<T: Reading> WindowRule<T> for MaxBound {
+    fn check(&self, sorted: &[T], index: usize, target: T) -> Result<(), CollapseError<T>> {
+        MaxBound::check_bounds(index, target, sorted[0], sorted[sorted.len() - 1])
+    }
+}
+
+/// Early warning. Twice the window minimum must not pass the reading, otherwise [`CollapseError::MinTooBig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MinBound;
+
+impl MinBound {
+    pub(crate) fn check_bounds<T: Reading>(
+        index: usize,
+        target: T,
+        min: T,
+        max: T,
+    ) -> Result<(), CollapseError<T>> {
+        match double_is_above(min, target) {
+            true => Err(CollapseError::MinTooBig {
+                index,
+                target,
+                min,
+                max,
+            }),
+            false => Ok(()),
+        }
+    }
+}
+
+impl<T: Reading> WindowRule<T> for MinBound {
+    fn check(&self, sorted: &[T], index: usize, target: T) -> Result<(), CollapseError<T>> {
+        MinBound::check_bounds(index, target, sorted[0], sorted[sorted.len() - 1])
+    }
+}
+
+///Both early warnings, maximum first, shared by every pair search.
+pub(crate) fn early_warnings<T: Reading>(
+    index: usize,
+    target: T,
+    min: T,
+    max: T,
+) -> Result<(), CollapseError<T>> {
+    MaxBound::check_bounds(index, target, min, max)?;
+    MinBound::check_bounds(index, target, min, max)
+}
+
+/// The reading must lie within `[min, 2 * max]` of the window, otherwise [`CollapseError::OutOfRange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WithinRange;
+
+impl<T: Reading> WindowRule<T> for WithinRange {
+    fn check(&self, sorted: &[T], index: usize, target: T) -> Result<(), CollapseError<T>> {
+        let min = sorted[0];
+        let max = sorted[sorted.len() - 1];
+        match target < min || double_is_below(max, target) {
+            true => Err(CollapseError::OutOfRange {
+                index,
+                target,
+                min,
+                max,
+            }),
+            false => Ok(()),
+        }
+    }
+}
+
+/// The reading must lie within this many standard deviations of the window mean,
+/// otherwise [`CollapseError::Outlier`]. The statistics are computed in `f64`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WithinDeviations(pub f64);
+
+impl<T: Reading> WindowRule<T> for WithinDeviations {
+    fn check(&self, sorted: &[T], index: usize, target: T) -> Result<(), CollapseError<T>> {
+        let count = sorted.len() as f64;
+        let mean = sorted.iter().map(|reading| reading.as_f64()).sum::<f64>() / count;
+        let variance = sorted
+            .iter()
+            .map(|reading| (reading.as_f64() - mean).powi(2))
+            .sum::<f64>()
+            / count; //O(n)
+        let std_dev = variance.sqrt();
+        match (target.as_f64() - mean).abs() <= self.0 * std_dev {
+            true => Ok(()),
+            false => Err(CollapseError::Outlier {
+                index,
+                target,
+                mean,
+                std_dev,
+            }),
+        }
+    }
+}
+
+/// A custom rule from a closure over the sorted window and the reading. Returning `false` reports
+/// [`CollapseError::Rejected`].
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check_rule, FnRule};
+/// //Readings may never repeat one in the window.
+/// let rule = FnRule(|sorted: &[u32], target: u32| sorted.binary_search(&target).is_err());
+/// assert!(collapse_check_rule(&[1, 2, 3, 4], 3, &rule).is_ok());
+/// assert!(collapse_check_rule(&[1, 2, 3, 2], 3, &rule).is_err());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FnRule<F>(pub F);
+
+impl<T: Reading, F: Fn(&[T], T) -> bool> WindowRule<T> for FnRule<F> {
+    fn check(&self, sorted: &[T], index: usize, target: T) -> Result<(), CollapseError<T>> {
+        match (self.0)(sorted, target) {
+            true => Ok(()),
+            false => Err(CollapseError::Rejected {
+                index,
+                target,
+                min: sorted[0],
+                max: sorted[sorted.len() - 1],
+            }),
+        }
+    }
+}
+
+/// The full pair check under this rule, early warnings included, as `PairRule::collapse_check` runs it.
+impl<T: Reading> WindowRule<T> for PairRule {
+    fn check(&self, sorted: &[T], index: usize, target: T) -> Result<(), CollapseError<T>> {
+        match *self {
+            PairRule::Legacy => pair_exists_two_pointer(sorted, index, target),
+            PairRule::Strict { distinct_values } => {
+                pair_exists_strict(sorted, index, target, distinct_values)
+            }
+        }
+    }
+}
+
+/// The full k-sum check, early warnings included, as `KSum::collapse_check` runs it, for any `k` of at least 1.
+impl<T: Reading> WindowRule<T> for KSum {
+    fn check(&self, sorted: &[T], index: usize, target: T) -> Result<(), CollapseError<T>> {
+        assert!(self.k > 0, "A sum of zero readings reaches nothing.");
+        ksum_check(sorted, self.k, self.distinct, index, target)
+    }
+}
+
+impl<T: Reading> WindowRule<T> for Vec<Box<dyn WindowRule<T>>> {
+    fn check(&self, sorted: &[T], index: usize, target: T) -> Result<(), CollapseError<T>> {
+        self.iter()
+            .try_for_each(|rule| rule.check(sorted, index, target))
+    }
+}
+
+macro_rules! impl_window_rule_for_tuple {
+    ($($rule:ident),*) => {
+        impl<T: Reading, $($rule: WindowRule<T>),*> WindowRule<T> for ($($rule,)*) {
+            #[allow(non_snake_case)]
+            fn check(&self, sorted: &[T], index: usize, target: T) -> Result<(), CollapseError<T>> {
+                let ($($rule,)*) = self;
+                $($rule.check(sorted, index, target)?;)*
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_window_rule_for_tuple!(A, B);
+impl_window_rule_for_tuple!(A, B, C);
+impl_window_rule_for_tuple!(A, B, C, D);
+
+/// Checks for imminent mine collapse of mining operation, under any [`WindowRule`].
+/// ### Arguments
+/// * `vector:` A vector
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// * `rule:` The condition every reading must meet.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_check, collapse_check_rule, MaxBound, MinBound, PairRule};
+/// let vector = vec![1, 1, 1, 4];
+/// assert_eq!(collapse_check_rule(&vector, 3, &PairRule::Legacy), collapse_check(&vector, 3));
+/// assert_eq!(
+///     collapse_check_rule(&vector, 3, &(MaxBound, MinBound)),
+///     collapse_check(&vector, 3)
+/// );
+/// ```
+pub fn collapse_check_rule<T: Reading, R: WindowRule<T> + ?Sized>(
+    vector: &[T],
+    period: usize,
+    rule: &R,
+) -> Result<(), CollapseError<T>> {
+    if vector.len() <= period {
+        return Ok(()); //Safe.
+    }
+    let mut window = IncrementalWindow::from_preamble(&vector[..period]);
+    for (c, &target) in vector.iter().enumerate().skip(period) {
+        rule.check(&window.sorted, c, target)?;
+        window.slide(vector[c - period], target);
+    }
+    Ok(())
+}
+
+/// `collapse_scan`, under any [`WindowRule`].
+/// Failing readings still enter the window, so every index is judged against exactly the `period` readings before it.
+pub fn collapse_scan_rule<T: Reading, R: WindowRule<T> + ?Sized>(
+    vector: &[T],
+    period: usize,
+    rule: &R,
+) -> Vec<CollapseError<T>> {
+    let mut anomalies = vec![];
+    if vector.len() <= period {
+        return anomalies; //Safe.
+    }
+    let mut window = IncrementalWindow::from_preamble(&vector[..period]);
+    for (c, &target) in vector.iter().enumerate().skip(period) {
+        if let Err(error) = rule.check(&window.sorted, c, target) {
+            anomalies.push(error);
+        }
+        window.slide(vector[c - period], target);
+    }
+    anomalies
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::strategy::PositionedWindow;
+use crate::{pair_position, CollapseError, Reading};
+
+/// The pair of window readings that validated the reading at `index`.
+///
+/// `a_index` and `b_index` are absolute positions in the input. They can be the same position
+/// when a reading is exactly twice an earlier one.
+/// `a + b == target` for every genuine sum. `collapse_check` also accepts `a - b == target`,
+/// see [`Witness::is_sum`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Witness<T = u128> {
+    pub index: usize,
+    pub target: T,
+    pub a_index: usize,
+    pub a: T,
+    pub b_index: usize,
+    pub b: T,
+}
+
+impl<T: Reading> Witness<T> {
+    /// `false` when the pair was accepted because `a - b == target` rather than `a + b == target`.
+    pub fn is_sum(&self) -> bool {
+        self.a.checked_add(self.b) == Some(self.target)
+    }
+}
+
+impl<T: Reading> fmt::Display for Witness<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = match self.is_sum() {
+            true => '+',
+            false => '-',
+        };
+        write!(
+            f,
+            "Index: {}, Element: {} = {} (Index: {}) {} {} (Index: {})",
+            self.index, self.target, self.a, self.a_index, operator, self.b, self.b_index
+        )
+    }
+}
+
+/// Same check as `pair_exists`, but returns the pair that matched.
+/// ### Arguments
+/// * `values:` The window, sorted by reading. The second element of each tuple is the position in the window, oldest first.
+/// * `index:` Position of `target` in the input. The window is assumed to be the `values.len()` readings right before it.
+/// * `target:` The reading being validated.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::pair_witness;
+/// let values = vec![(1, 1), (2, 0), (5, 2)];
+/// let witness = pair_witness(&values, 3, 7).unwrap();
+/// assert_eq!((witness.a_index, witness.b_index), (0, 2));
+/// assert_eq!((witness.a, witness.b), (2, 5));
+/// ```
+pub fn pair_witness<T: Reading>(
+    values: &[(T, usize)],
+    index: usize,
+    target: T,
+) -> Result<Witness<T>, CollapseError<T>> {
+    let (a, b) = pair_position(values, index, target)?;
+    let start = index - values.len();
+    Ok(Witness {
+        index,
+        target,
+        a_index: start + values[a].1,
+        a: values[a].0,
+        b_index: start + values[b].1,
+        b: values[b].0,
+    })
+}
+
+/// Runs `collapse_check`, but keeps a [`Witness`] for every reading after the first `period`.
+/// Stops at the first failing reading, like `collapse_check`.
+/// ### Arguments
+/// * `vector:` A vector
+/// * `period:` Determines the range `i..i+k`, where i+k is not included.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::collapse_witnesses;
+/// let vector = vec![1, 2, 3, 4];
+/// let witnesses = collapse_witnesses(&vector, 3).unwrap();
+/// assert_eq!(witnesses.len(), 1);
+/// assert_eq!((witnesses[0].a_index, witnesses[0].b_index), (0, 2));
+/// ```
+pub fn collapse_witnesses<T: Reading>(
+    vector: &[T],
+    period: usize,
+) -> Result<Vec<Witness<T>>, CollapseError<T>> {
+    let mut witnesses = vec![];
+    if vector.len() <= period {
+        return Ok(witnesses); //Safe.
+    }
+    let mut window = PositionedWindow::from_preamble(&vector[..period]);
+    for (c, &target) in vector.iter().enumerate().skip(period) {
+        witnesses.push(pair_witness(window.sorted(), c, target)?);
+        window.slide(target);
+    }
+    Ok(witnesses)
+}
+
+/// Writes `witnesses` as CSV, one row per validated reading, with a header row.
+/// ### Example
+/// ```
+/// use namt_preventative_collapse::{collapse_witnesses, write_witness_report};
+/// let witnesses = collapse_witnesses(&[1, 2, 3, 4], 3).unwrap();
+/// let mut report = vec![];
+/// write_witness_report(&witnesses, &mut report).unwrap();
+/// assert_eq!(
+///     String::from_utf8(report).unwrap(),
+///     "index,target,a_index,a,b_index,b\n3,4,0,1,2,3\n"
+/// );
+/// ```
+pub fn write_witness_report<T: Reading, W: Write>(
+    witnesses: &[Witness<T>],
+    mut writer: W,
+) -> io::Result<()> {
+    writeln!(writer, "index,target,a_index,a,b_index,b")?;
+    for witness in witnesses {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{}",
+            witness.index, witness.target, witness.a_index, witness.a, witness.b_index, witness.b
+        )?;
+    }
+    Ok(())
+}
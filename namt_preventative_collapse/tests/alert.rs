@@ -0,0 +1,231 @@
+mod common;
+
+use common::read;
+use namt_preventative_collapse::{CollapseError, Verdict};
+
+///Verdicts from a pattern, `.` for safe, `x` for no pair found and `!` for an early warning.
+fn verdicts(pattern: &str) -> Vec<Result<Verdict, CollapseError>> {
+    pattern
+        .chars()
+        .enumerate()
+        .map(|(index, c)| match c {
+            '.' => Ok(Verdict::Safe),
+            'x' => Err(CollapseError::NoPairFound {
+                index,
+                target: 1,
+                min: 2,
+                max: 3,
+            }),
+            '!' => Err(CollapseError::MaxTooSmall {
+                index,
+                target: 100,
+                min: 2,
+                max: 3,
+            }),
+            _ => Ok(Verdict::Warming),
+        })
+        .collect()
+}
+
+///The index and new level of every alert raised or cleared over `pattern`.
+fn levels(
+    pattern: &str,
+    policy: namt_preventative_collapse::AlertPolicy,
+) -> Vec<(usize, Option<namt_preventative_collapse::Severity>)> {
+    use namt_preventative_collapse::AlertTracker;
+    let mut tracker = AlertTracker::new(policy);
+    verdicts(pattern)
+        .iter()
+        .filter_map(|result| tracker.observe(result))
+        .map(|alert| (alert.index, alert.to))
+        .collect()
+}
+
+#[test]
+fn isolated_anomaly_is_info() {
+    use namt_preventative_collapse::{AlertPolicy, Severity::*};
+    assert_eq!(
+        levels("....x......", AlertPolicy::default()),
+        vec![(4, Some(Info)), (9, None)]
+    );
+    assert_eq!(levels("..........", AlertPolicy::default()), vec![]);
+}
+
+#[test]
+fn anomalies_within_span_warn() {
+    use namt_preventative_collapse::{AlertPolicy, Severity::*};
+    //Three anomalies within 10 readings, but never 3 in a row.
+    assert_eq!(
+        levels("x...x...x...........", AlertPolicy::default()),
+        vec![(0, Some(Info)), (8, Some(Warning)), (14, None)]
+    );
+    //Too far apart to warn, each one is cleared before the next.
+    assert_eq!(
+        levels("x.....x.....x", AlertPolicy::default()),
+        vec![
+            (0, Some(Info)),
+            (5, None),
+            (6, Some(Info)),
+            (11, None),
+            (12, Some(Info))
+        ]
+    );
+}
+
+#[test]
+fn consecutive_failures_are_critical() {
+    use namt_preventative_collapse::{AlertPolicy, Severity::*};
+    assert_eq!(
+        levels("..xxx", AlertPolicy::default()),
+        vec![(2, Some(Info)), (4, Some(Critical))]
+    );
+    let policy = AlertPolicy {
+        warning_anomalies: 4,
+        critical_consecutive: 2,
+        ..AlertPolicy::default()
+    };
+    assert_eq!(
+        levels("x.xx", policy),
+        vec![(0, Some(Info)), (3, Some(Critical))]
+    );
+}
+
+#[test]
+fn early_warnings_are_critical_unless_configured() {
+    use namt_preventative_collapse::{AlertPolicy, Severity::*};
+    assert_eq!(
+        levels("...!", AlertPolicy::default()),
+        vec![(3, Some(Critical))]
+    );
+    let policy = AlertPolicy {
+        early_warning: Warning,
+        ..AlertPolicy::default()
+    };
+    assert_eq!(levels("...!", policy), vec![(3, Some(Warning))]);
+}
+
+#[test]
+fn transient_early_warning_pages_at_once() {
+    use namt_preventative_collapse::{AlertPolicy, Severity::*};
+    //One spike pages Critical, and the page holds for clear_after readings.
+    assert_eq!(
+        levels("...!.........", AlertPolicy::default()),
+        vec![(3, Some(Critical)), (8, None)]
+    );
+    let policy = AlertPolicy {
+        early_warning: Info,
+        ..AlertPolicy::default()
+    };
+    assert_eq!(
+        levels("...!.........", policy),
+        vec![(3, Some(Info)), (8, None)]
+    );
+}
+
+#[test]
+fn hysteresis_holds_the_level() {
+    use namt_preventative_collapse::{AlertPolicy, AlertTracker, Severity::*};
+    //One safe reading in the middle of the failures does not stand the shift down.
+    assert_eq!(
+        levels("xxx.xxx......", AlertPolicy::default()),
+        vec![(0, Some(Info)), (2, Some(Critical)), (11, Some(Warning))]
+    );
+    let policy = AlertPolicy {
+        clear_after: 3,
+        ..AlertPolicy::default()
+    };
+    //A reading graded at the level restarts the count of readings needed to clear.
+    let mut tracker = AlertTracker::new(policy);
+    for result in verdicts("xxx..!..") {
+        tracker.observe(&result);
+    }
+    assert_eq!(tracker.level(), Some(Critical));
+    //A clear_after of zero lowers the level at the first reading below it.
+    let policy = AlertPolicy {
+        clear_after: 0,
+        ..AlertPolicy::default()
+    };
+    assert_eq!(levels("x.", policy), vec![(0, Some(Info)), (1, None)]);
+}
+
+#[test]
+fn warming_readings_are_ignored() {
+    use namt_preventative_collapse::{AlertPolicy, Severity::*};
+    let policy = AlertPolicy {
+        clear_after: 2,
+        ..AlertPolicy::default()
+    };
+    //Warming readings neither clear the alert nor count towards the span.
+    assert_eq!(levels("x~~~.~.", policy), vec![(0, Some(Info)), (6, None)]);
+}
+
+#[test]
+fn alerts_carry_their_cause() {
+    use namt_preventative_collapse::{collapse_alerts, AlertPolicy, CollapseError, Severity};
+    let numbers = read("../test_inputs/mini_mine.txt");
+    let alerts = collapse_alerts(&numbers, 5, AlertPolicy::default());
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[0].index, 14);
+    assert_eq!(alerts[0].from, None);
+    assert_eq!(alerts[0].to, Some(Severity::Critical));
+    assert_eq!(
+        alerts[0].cause,
+        Some(CollapseError::MinTooBig {
+            index: 14,
+            target: 127,
+            min: 95,
+            max: 182
+        })
+    );
+    //Cleared after the 5 safe readings that follow.
+    assert_eq!(alerts[1].index, 19);
+    assert_eq!(alerts[1].to, None);
+    assert_eq!(alerts[1].cause, None);
+    assert_eq!(alerts[1].to_string(), "Cleared at index 19.");
+    assert!(alerts[0].to_string().starts_with("Critical: "));
+}
+
+#[test]
+fn challenge_input_is_a_single_info() {
+    use namt_preventative_collapse::{collapse_alerts, AlertPolicy, Severity};
+    let numbers = read("../test_inputs/challenge_input.txt");
+    let alerts = collapse_alerts(&numbers, 100, AlertPolicy::default());
+    let levels: Vec<_> = alerts.iter().map(|alert| (alert.index, alert.to)).collect();
+    assert_eq!(levels, vec![(5231, Some(Severity::Info)), (5236, None)]);
+}
+
+#[test]
+fn tracks_a_timed_monitor() {
+    use namt_preventative_collapse::{AlertPolicy, AlertTracker, Severity, TimedMonitor};
+    use std::time::Duration;
+    let seconds = Duration::from_secs;
+    let mut monitor = TimedMonitor::new(seconds(10));
+    let mut tracker = AlertTracker::new(AlertPolicy::default());
+    for (time, reading) in [(0, 1), (10, 2), (40, 3), (50, 6), (60, 12), (59, 1)] {
+        tracker.observe(&monitor.push(seconds(time), reading));
+    }
+    //An empty window, then a reading out of order, after a safe one, is not enough to warn.
+    assert_eq!(tracker.level(), Some(Severity::Info));
+    assert_eq!(tracker.policy(), AlertPolicy::default());
+}
+
+#[test]
+#[should_panic]
+fn span_smaller_than_anomalies_panics() {
+    use namt_preventative_collapse::{AlertPolicy, AlertTracker};
+    AlertTracker::new(AlertPolicy {
+        warning_anomalies: 5,
+        warning_span: 4,
+        ..AlertPolicy::default()
+    });
+}
+
+#[test]
+#[should_panic]
+fn zero_consecutive_panics() {
+    use namt_preventative_collapse::{AlertPolicy, AlertTracker};
+    AlertTracker::new(AlertPolicy {
+        critical_consecutive: 0,
+        ..AlertPolicy::default()
+    });
+}
@@ -0,0 +1,462 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], stdin: Option<&str>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_namt_preventative_collapse"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut input = child.stdin.take().unwrap();
+    if let Err(error) = input.write_all(stdin.unwrap_or("").as_bytes()) {
+        //The binary can exit on bad arguments before it reads its input.
+        assert_eq!(error.kind(), std::io::ErrorKind::BrokenPipe);
+    }
+    drop(input);
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn challenge_input_collapses() {
+    let output = run(&["../test_inputs/challenge_input.txt"], None);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = stdout(&output);
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout
+        .starts_with("Imminent Failure detected. No pairs reach the required sum. Index: 5231,"));
+    //The library does not print, so the report is the only output.
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn safe_input_exits_zero() {
+    use std::fs;
+    let numbers = fs::read_to_string("../test_inputs/challenge_input.txt").unwrap();
+    let safe: String = numbers
+        .lines()
+        .take(5231)
+        .map(|x| format!("{}\n", x))
+        .collect();
+    let output = run(&["-", "--period", "100"], Some(&safe));
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "Safe. No collapse detected in 5231 readings with period 100.\n"
+    );
+}
+
+#[test]
+fn all_reports_every_anomaly_as_csv() {
+    let output = run(
+        &[
+            "--period",
+            "3",
+            "--all",
+            "--format",
+            "csv",
+            "--strategy",
+            "hashed",
+            "-",
+        ],
+        Some("1\n1\n1\n4\n2\n9\n"),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "index,kind,target,min,max\n3,MaxTooSmall,4,1,1\n5,MaxTooSmall,9,1,4\n"
+    );
+}
+
+#[test]
+fn json_format() {
+    let output = run(
+        &[
+            "../test_inputs/mini_mine.txt",
+            "--period",
+            "5",
+            "--format",
+            "json",
+        ],
+        None,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "{\"readings\":20,\"min_reading\":15,\"max_reading\":576,\"period\":5,\"strategy\":\"resort\",\"safe\":false,\"anomalies\":[{\"kind\":\"MinTooBig\",\"index\":14,\"target\":127,\"min\":95,\"max\":182}]}\n"
+    );
+
+    let output = run(
+        &["-", "--format", "json", "--period", "3"],
+        Some("1\n2\n3\n4\n"),
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "{\"readings\":4,\"min_reading\":1,\"max_reading\":4,\"period\":3,\"strategy\":\"resort\",\"safe\":true,\"anomalies\":[]}\n"
+    );
+}
+
+#[test]
+fn json_lines_format() {
+    let output = run(
+        &[
+            "../test_inputs/mini_mine.txt",
+            "--period",
+            "5",
+            "--all",
+            "--format",
+            "jsonl",
+        ],
+        None,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "{\"kind\":\"MinTooBig\",\"index\":14,\"target\":127,\"min\":95,\"max\":182}\n\
+         {\"readings\":20,\"min_reading\":15,\"max_reading\":576,\"period\":5,\"strategy\":\"resort\",\"safe\":false}\n"
+    );
+
+    let output = run(
+        &["-", "--format", "jsonl", "--period", "3", "--witnesses"],
+        Some("1\n2\n3\n4\n5\n"),
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "{\"index\":3,\"target\":4,\"a_index\":0,\"a\":1,\"b_index\":2,\"b\":3}\n\
+         {\"index\":4,\"target\":5,\"a_index\":1,\"a\":2,\"b_index\":2,\"b\":3}\n\
+         {\"readings\":5,\"min_reading\":1,\"max_reading\":5,\"period\":3,\"strategy\":\"resort\",\"safe\":true}\n"
+    );
+
+    let output = run(
+        &[
+            "../test_inputs/mine_log.csv",
+            "--window",
+            "5m",
+            "--format",
+            "jsonl",
+        ],
+        None,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "{\"record\":28,\"timestamp\":\"2024-03-01T06:14:00Z\",\"sensor\":\"B7\",\"kind\":\"MinTooBig\",\"index\":14,\"target\":127,\"min\":95,\"max\":182}\n\
+         {\"readings\":40,\"sensors\":2,\"min_reading\":1,\"max_reading\":576,\"window_seconds\":300.0,\"safe\":false}\n"
+    );
+
+    let output = run(&["-", "--csv", "--witnesses"], Some("06:00,B7,1\n"));
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn strategies_agree() {
+    let expected = stdout(&run(&["../test_inputs/mega_mine.txt", "--all"], None));
+    for strategy in ["resort", "hashed", "incremental"] {
+        let output = run(
+            &[
+                "../test_inputs/mega_mine.txt",
+                "--all",
+                "--strategy",
+                strategy,
+            ],
+            None,
+        );
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(stdout(&output), expected);
+    }
+}
+
+#[test]
+fn bad_input_is_an_input_error() {
+    let output = run(&["-", "--period", "2"], Some("1\n2\nthree\n"));
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("Line 3, column 1:"));
+
+    let output = run(&["../test_inputs/does_not_exist.txt"], None);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn bad_arguments_are_an_input_error() {
+    for args in [
+        vec![],
+        vec!["-", "--period", "0"],
+        vec!["-", "--period"],
+        vec!["-", "--strategy", "bogus"],
+        vec!["-", "--format", "xml"],
+        vec!["-", "--verbose"],
+        vec!["-", "extra.txt"],
+    ] {
+        let output = run(&args, Some("1\n2\n3\n"));
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(output.stdout.is_empty());
+    }
+}
+
+#[test]
+fn help() {
+    let output = run(&["--help"], None);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("Exit codes:"));
+}
+
+#[test]
+fn lenient_skips_malformed_lines() {
+    let input = "# Shaft B\r\n1\r\n2\r\n\r\nthree\r\n3\r\n0x4\r\n";
+    let output = run(&["-", "--period", "2"], Some(input));
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Line 5, column 1: Invalid digit in reading.\n"
+    );
+
+    let output = run(&["-", "--period", "2", "--lenient"], Some(input));
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "Safe. No collapse detected in 4 readings with period 2.\n"
+    );
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .ends_with("Skipped 1 malformed lines.\n"));
+}
+
+#[test]
+fn csv_input_reports_timestamp_and_sensor() {
+    let output = run(
+        &["../test_inputs/mine_log.csv", "--csv", "--period", "5"],
+        None,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "Sensor B7 at 2024-03-01T06:14:00Z: Imminent Failure: Minimum is too big to reach the required sum. Index: 14, Element: 127, Window min: 95, Window max: 182\n"
+    );
+
+    let output = run(
+        &[
+            "../test_inputs/mine_log.csv",
+            "--value-column",
+            "value",
+            "--period",
+            "5",
+            "--all",
+            "--format",
+            "csv",
+        ],
+        None,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "timestamp,sensor,index,kind,target,min,max\n2024-03-01T06:14:00Z,B7,14,MinTooBig,127,95,182\n"
+    );
+}
+
+#[test]
+fn csv_options() {
+    let input =
+        "35;\"Shaft \"\"B\"\"\";2024-03-01 06:00\n36;\"Shaft \"\"B\"\"\";2024-03-01 06:01\n";
+    let output = run(
+        &[
+            "-",
+            "--delimiter",
+            ";",
+            "--header",
+            "no",
+            "--value-column",
+            "1",
+            "--sensor-column",
+            "2",
+            "--timestamp-column",
+            "3",
+            "--period",
+            "1",
+            "--format",
+            "json",
+        ],
+        Some(input),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "{\"readings\":2,\"sensors\":1,\"min_reading\":35,\"max_reading\":36,\"period\":1,\"strategy\":\"resort\",\"safe\":false,\"anomalies\":[{\"record\":1,\"timestamp\":\"2024-03-01 06:01\",\"sensor\":\"Shaft \\\"B\\\"\",\"kind\":\"MinTooBig\",\"index\":1,\"target\":36,\"min\":35,\"max\":35}]}\n"
+    );
+
+    let output = run(
+        &["-", "--csv", "--delimiter", "tab"],
+        Some("06:00\tB7\t1\n"),
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "Safe. No collapse detected in 1 readings from 1 sensors with period 100.\n"
+    );
+
+    let output = run(&["-", "--csv"], Some("06:00,B7,1\n06:01,B7\n"));
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Line 2, column 9: Row has no field in the selected column.\n"
+    );
+
+    for args in [
+        vec!["-", "--csv", "--lenient"],
+        vec!["-", "--delimiter", "::"],
+        vec!["-", "--header", "maybe"],
+        vec!["-", "--value-column", "0"],
+    ] {
+        let output = run(&args, Some("06:00,B7,1\n"));
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+    }
+}
+
+#[test]
+fn time_window() {
+    let expected = stdout(&run(
+        &[
+            "../test_inputs/mine_log.csv",
+            "--csv",
+            "--period",
+            "4",
+            "--all",
+        ],
+        None,
+    ));
+    for window in ["4m", "240s", "240"] {
+        let output = run(
+            &["../test_inputs/mine_log.csv", "--window", window, "--all"],
+            None,
+        );
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(stdout(&output), expected);
+    }
+
+    let input = "0,B7,1\n1,B7,2\n2,B7,3\n30,B7,3\n29,B7,3\n";
+    let output = run(
+        &["-", "--window", "2s", "--all", "--format", "csv"],
+        Some(input),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "timestamp,sensor,index,kind,target,min,max\n30,B7,3,EmptyWindow,3,,\n29,B7,4,BadTimestamp,3,,\n"
+    );
+
+    let output = run(&["-", "--window", "1h"], Some("0,B7,1\n"));
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "Safe. No collapse detected in 1 readings from 1 sensors with window 3600s.\n"
+    );
+
+    for args in [
+        vec!["-", "--window", "10m", "--period", "5"],
+        vec!["-", "--window", "0s"],
+        vec!["-", "--window", "10 minutes"],
+        vec!["-", "--window", "10m", "--lenient"],
+    ] {
+        let output = run(&args, Some("0,B7,1\n"));
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+    }
+}
+
+#[test]
+fn generated_series_match_their_ground_truth() {
+    use std::fs;
+    let directory = std::env::temp_dir();
+    let data = directory.join(format!("namt_generate_{}.txt", std::process::id()));
+    let data = data.to_str().unwrap();
+    let output = run(
+        &[
+            "generate",
+            "--period",
+            "7",
+            "--length",
+            "2000",
+            "--seed",
+            "11",
+            "--anomalies",
+            "6",
+            "--at",
+            "100:too-small",
+            data,
+        ],
+        None,
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+    //Written next to the data.
+    let truth_path = data.replace(".txt", ".anomalies.csv");
+    let truth = fs::read_to_string(&truth_path).unwrap();
+    assert_eq!(truth.lines().count(), 8);
+    assert!(truth.contains("\n100,MinTooBig,0,"));
+
+    let output = run(&[data, "--period", "7", "--all", "--format", "csv"], None);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), truth);
+    fs::remove_file(data).unwrap();
+    fs::remove_file(truth_path).unwrap();
+
+    //Standard output, without ground truth unless asked for.
+    let output = run(&["generate", "--length", "150", "-"], None);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output).lines().count(), 150);
+    let safe = run(&["-"], Some(&stdout(&output)));
+    assert_eq!(safe.status.code(), Some(0));
+}
+
+#[test]
+fn generate_rejects_bad_anomalies() {
+    for (args, message) in [
+        (
+            vec!["generate", "--at", "120:boom", "-"],
+            "Unknown anomaly kind boom.",
+        ),
+        (
+            vec!["generate", "--at", "120", "-"],
+            "--at 120 must be INDEX:KIND",
+        ),
+        (
+            vec!["generate", "--at", "50:no-pair", "-"],
+            "--at 50 is not a checked reading",
+        ),
+        (
+            vec![
+                "generate",
+                "--at",
+                "150:no-pair",
+                "--at",
+                "200:too-large",
+                "-",
+            ],
+            "--at 150 and 200 must be more than the period apart.",
+        ),
+        (vec!["generate", "--period", "2", "-"], "--period must be"),
+        (
+            vec![
+                "generate",
+                "--max-reading",
+                "113427455640312821154458202477256070485",
+                "-",
+            ],
+            "--max-reading must be a whole number from 2 to 113427455640312821154458202477256070484.",
+        ),
+    ] {
+        let output = run(&args, None);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(output.stdout.is_empty());
+        assert!(String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with(message));
+    }
+}
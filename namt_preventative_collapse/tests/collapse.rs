@@ -1,23 +1,23 @@
 #[test]
 fn early_warning_system_all_values_too_small() {
-    let mut vec = vec![2, 1, 2, 7];
+    let vec = vec![2, 1, 2, 7];
     use namt_preventative_collapse::collapse_check;
-    let result = collapse_check(&mut vec, 3);
+    let result = collapse_check(&vec, 3);
     assert_ne!(result, Ok(()));
 }
 
 #[test]
 fn early_warning_system_all_values_too_big() {
-    let mut vec = vec![6, 4, 5, 7];
+    let vec = vec![6, 4, 5, 7];
     use namt_preventative_collapse::collapse_check;
-    let result = collapse_check(&mut vec, 3);
+    let result = collapse_check(&vec, 3);
     assert_ne!(result, Ok(()));
 }
 #[test]
 fn general_warning_system() {
-    let mut vec = vec![2, 1, 3, 5, 4, 7, 13];
+    let vec = vec![2, 1, 3, 5, 4, 7, 13];
     use namt_preventative_collapse::collapse_check;
-    let result = collapse_check(&mut vec, 3);
+    let result = collapse_check(&vec, 3);
     if !result.is_ok() {
         eprintln!("{:?}", result);
     }
@@ -26,11 +26,12 @@ fn general_warning_system() {
 
 #[test]
 fn mini_mine() {
+    use namt_preventative_collapse::parse_readings;
     use std::fs;
     let numbers = fs::read_to_string("../test_inputs/mini_mine.txt");
-    let mut numbers = Vec::from_iter(numbers.unwrap().lines().map(|x| x.parse::<u128>().unwrap()));
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
     use namt_preventative_collapse::collapse_check;
-    let result = collapse_check(&mut numbers, 5);
+    let result = collapse_check(&numbers, 5);
 
     if !result.is_ok() {
         eprintln!("{:?}", result);
@@ -39,11 +40,12 @@ fn mini_mine() {
 }
 #[test]
 fn mega_mine() {
+    use namt_preventative_collapse::parse_readings;
     use std::fs;
-    let numbers = fs::read_to_string("..\\test_inputs\\mega_mine.txt");
-    let mut numbers = Vec::from_iter(numbers.unwrap().lines().map(|x| x.parse::<u128>().unwrap()));
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
     use namt_preventative_collapse::collapse_check;
-    let result = collapse_check(&mut numbers, 100);
+    let result = collapse_check(&numbers, 100);
 
     if !result.is_ok() {
         eprintln!("{:?}", result);
@@ -53,11 +55,12 @@ fn mega_mine() {
 
 #[test]
 fn mega_mine_bench_verify_alloc_is_slow() {
+    use namt_preventative_collapse::parse_readings;
     use std::fs;
-    let numbers = fs::read_to_string("..\\test_inputs\\mega_mine.txt");
-    let mut numbers = Vec::from_iter(numbers.unwrap().lines().map(|x| x.parse::<u128>().unwrap()));
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
     use namt_preventative_collapse::bench_iterations::collapse_check_alloc_and_sort_on_each_loop;
-    let result = collapse_check_alloc_and_sort_on_each_loop(&mut numbers, 100);
+    let result = collapse_check_alloc_and_sort_on_each_loop(&numbers, 100);
 
     if !result.is_ok() {
         eprintln!("{:?}", result);
@@ -67,12 +70,13 @@ fn mega_mine_bench_verify_alloc_is_slow() {
 
 #[test]
 fn mega_mine_are_references_better() {
+    use namt_preventative_collapse::parse_readings;
     use std::fs;
-    let numbers = fs::read_to_string("..\\test_inputs\\mega_mine.txt");
-    let mut numbers = Vec::from_iter(numbers.unwrap().lines().map(|x| x.parse::<u128>().unwrap()));
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
     use namt_preventative_collapse::bench_iterations::collapse_check_are_references_better;
 
-    let result = collapse_check_are_references_better(&mut numbers, 100);
+    let result = collapse_check_are_references_better(&numbers, 100);
 
     if !result.is_ok() {
         eprintln!("{:?}", result);
@@ -83,9 +87,9 @@ fn mega_mine_are_references_better() {
 
 #[test]
 fn refs_early_warning_system_all_values_too_small() {
-    let mut vec = vec![2, 1, 2, 7];
+    let vec = vec![2, 1, 2, 7];
     use namt_preventative_collapse::bench_iterations::collapse_check_are_references_better;
-    let result = collapse_check_are_references_better(&mut vec, 3);
+    let result = collapse_check_are_references_better(&vec, 3);
     if !result.is_ok() {
         eprintln!("{:?}", result);
     }
@@ -94,9 +98,9 @@ fn refs_early_warning_system_all_values_too_small() {
 
 #[test]
 fn refs_early_warning_system_all_values_too_big() {
-    let mut vec = vec![6, 4, 5, 7];
+    let vec = vec![6, 4, 5, 7];
     use namt_preventative_collapse::bench_iterations::collapse_check_are_references_better;
-    let result = collapse_check_are_references_better(&mut vec, 3);
+    let result = collapse_check_are_references_better(&vec, 3);
     if !result.is_ok() {
         eprintln!("{:?}", result);
     }
@@ -104,9 +108,9 @@ fn refs_early_warning_system_all_values_too_big() {
 }
 #[test]
 fn refs_general_warning_system() {
-    let mut vec = vec![2, 1, 3, 5, 4, 7, 13];
+    let vec = vec![2, 1, 3, 5, 4, 7, 13];
     use namt_preventative_collapse::bench_iterations::collapse_check_are_references_better;
-    let result = collapse_check_are_references_better(&mut vec, 3);
+    let result = collapse_check_are_references_better(&vec, 3);
     if !result.is_ok() {
         eprintln!("{:?}", result);
     }
@@ -115,11 +119,12 @@ fn refs_general_warning_system() {
 
 #[test]
 fn refs_mini_mine() {
+    use namt_preventative_collapse::parse_readings;
     use std::fs;
     let numbers = fs::read_to_string("../test_inputs/mini_mine.txt");
-    let mut numbers = Vec::from_iter(numbers.unwrap().lines().map(|x| x.parse::<u128>().unwrap()));
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
     use namt_preventative_collapse::bench_iterations::collapse_check_are_references_better;
-    let result = collapse_check_are_references_better(&mut numbers, 5);
+    let result = collapse_check_are_references_better(&numbers, 5);
 
     if !result.is_ok() {
         eprintln!("{:?}", result);
@@ -129,12 +134,13 @@ fn refs_mini_mine() {
 
 #[test]
 fn mega_mine_with_more_short_circuiting() {
+    use namt_preventative_collapse::parse_readings;
     use std::fs;
-    let numbers = fs::read_to_string("..\\test_inputs\\mega_mine.txt");
-    let mut numbers = Vec::from_iter(numbers.unwrap().lines().map(|x| x.parse::<u128>().unwrap()));
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
     use namt_preventative_collapse::bench_iterations::collapse_check_with_more_short_circuiting;
 
-    let result = collapse_check_with_more_short_circuiting(&mut numbers, 100);
+    let result = collapse_check_with_more_short_circuiting(&numbers, 100);
 
     if !result.is_ok() {
         eprintln!("{:?}", result);
@@ -144,15 +150,126 @@ fn mega_mine_with_more_short_circuiting() {
 
 #[test]
 fn mega_mine_narrowing_search() {
+    use namt_preventative_collapse::parse_readings;
     use std::fs;
-    let numbers = fs::read_to_string("..\\test_inputs\\mega_mine.txt");
-    let mut numbers = Vec::from_iter(numbers.unwrap().lines().map(|x| x.parse::<u128>().unwrap()));
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
     use namt_preventative_collapse::bench_iterations::collapse_check_narrowing_search;
 
-    let result = collapse_check_narrowing_search(&mut numbers, 100);
+    let result = collapse_check_narrowing_search(&numbers, 100);
 
     if !result.is_ok() {
         eprintln!("{:?}", result);
     }
     assert_ne!(result, Ok(()));
 }
+
+#[test]
+fn error_kinds_are_distinguishable() {
+    use namt_preventative_collapse::{collapse_check, CollapseError};
+    let vec = vec![2, 1, 2, 7];
+    let result = collapse_check(&vec, 3);
+    assert_eq!(
+        result,
+        Err(CollapseError::MaxTooSmall {
+            index: 3,
+            target: 7,
+            min: 1,
+            max: 2
+        })
+    );
+
+    let vec = vec![6, 4, 5, 7];
+    let result = collapse_check(&vec, 3);
+    assert_eq!(
+        result,
+        Err(CollapseError::MinTooBig {
+            index: 3,
+            target: 7,
+            min: 4,
+            max: 6
+        })
+    );
+
+    let vec = vec![2, 1, 3, 5, 4, 7, 13];
+    let result = collapse_check(&vec, 3);
+    assert_eq!(
+        result,
+        Err(CollapseError::NoPairFound {
+            index: 6,
+            target: 13,
+            min: 4,
+            max: 7
+        })
+    );
+}
+
+#[test]
+fn mega_mine_error_is_shared_by_bench_iterations() {
+    use namt_preventative_collapse::parse_readings;
+    use std::fs;
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
+    use namt_preventative_collapse::bench_iterations::*;
+    use namt_preventative_collapse::collapse_check;
+
+    let expected = collapse_check(&numbers, 100);
+    assert_eq!(expected.as_ref().map_err(|error| error.index()), Err(5231));
+    assert_eq!(
+        collapse_check_are_references_better(&numbers, 100),
+        expected
+    );
+    assert_eq!(collapse_check_no_short_ciruit(&numbers, 100), expected);
+}
+
+#[test]
+fn scan_reports_every_anomaly() {
+    use namt_preventative_collapse::{collapse_scan, CollapseError};
+    let vec = vec![2, 1, 3, 5, 4, 7, 13, 11, 40, 18];
+    let anomalies = collapse_scan(&vec, 3);
+    assert_eq!(
+        anomalies,
+        vec![
+            CollapseError::NoPairFound {
+                index: 6,
+                target: 13,
+                min: 4,
+                max: 7
+            },
+            CollapseError::MaxTooSmall {
+                index: 8,
+                target: 40,
+                min: 7,
+                max: 13
+            },
+            CollapseError::MinTooBig {
+                index: 9,
+                target: 18,
+                min: 11,
+                max: 40
+            },
+        ]
+    );
+}
+
+#[test]
+fn scan_starts_with_collapse_check_result() {
+    use namt_preventative_collapse::parse_readings;
+    use namt_preventative_collapse::{collapse_check, collapse_scan};
+    use std::fs;
+    for (path, period) in [
+        ("../test_inputs/mini_mine.txt", 5),
+        ("../test_inputs/challenge_input.txt", 100),
+    ] {
+        let numbers = fs::read_to_string(path);
+        let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
+        let anomalies = collapse_scan(&numbers, period);
+        assert_eq!(collapse_check(&numbers, period), Err(anomalies[0].clone()));
+    }
+}
+
+#[test]
+fn scan_of_short_series_is_empty() {
+    use namt_preventative_collapse::collapse_scan;
+    assert!(collapse_scan(&[1, 2, 3], 3).is_empty());
+}
@@ -0,0 +1,38 @@
+//! Helpers shared by the integration tests. Each test crate only uses some of them.
+#![allow(dead_code)]
+
+///The readings of one of the bundled test inputs.
+pub fn read(path: &str) -> Vec<u128> {
+    use namt_preventative_collapse::parse_readings;
+    parse_readings::<u128>(&read_text(path)).unwrap()
+}
+
+///The text of one of the bundled test inputs.
+pub fn read_text(path: &str) -> String {
+    use std::fs;
+    fs::read_to_string(path).unwrap()
+}
+
+///Small deterministic series with plenty of duplicates, zeroes and differences.
+pub fn pseudo_random_series(seed: u64, len: usize, modulus: u64) -> Vec<u128> {
+    lcg(seed, len, modulus).map(u128::from).collect()
+}
+
+///`pseudo_random_series` as signed readings, centred on zero when `signed`.
+pub fn pseudo_random_signed_series(seed: u64, len: usize, modulus: u64, signed: bool) -> Vec<i128> {
+    let offset = if signed { (modulus / 2) as i128 } else { 0 };
+    lcg(seed, len, modulus)
+        .map(|reading| reading as i128 - offset)
+        .collect()
+}
+
+///`len` values in `0..modulus`, from a linear congruential generator.
+fn lcg(seed: u64, len: usize, modulus: u64) -> impl Iterator<Item = u64> {
+    let mut state = seed;
+    (0..len).map(move |_| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) % modulus
+    })
+}
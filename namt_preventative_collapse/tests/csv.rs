@@ -0,0 +1,191 @@
+mod common;
+
+use common::read_text;
+
+#[test]
+fn mine_log_checks_each_sensor_as_its_own_series() {
+    use namt_preventative_collapse::{
+        collapse_check_records, collapse_scan, collapse_scan_records, parse_csv, parse_readings,
+        CsvFormat, Strategy,
+    };
+    let records = parse_csv::<u128>(
+        &read_text("../test_inputs/mine_log.csv"),
+        &CsvFormat::default(),
+    )
+    .unwrap();
+    assert_eq!(records.len(), 40);
+    let b7: Vec<u128> = records
+        .iter()
+        .filter(|record| record.sensor == "B7")
+        .map(|record| record.value)
+        .collect();
+    assert_eq!(
+        b7,
+        parse_readings::<u128>(&read_text("../test_inputs/mini_mine.txt")).unwrap()
+    );
+
+    for strategy in [Strategy::Resort, Strategy::Hashed, Strategy::Incremental] {
+        let violations = collapse_scan_records(&records, 5, strategy);
+        let errors: Vec<_> = violations
+            .iter()
+            .map(|violation| violation.error.clone())
+            .collect();
+        assert_eq!(errors, collapse_scan(&b7, 5));
+        assert_eq!(violations[0].record, 28);
+        assert_eq!(violations[0].timestamp, "2024-03-01T06:14:00Z");
+        assert_eq!(violations[0].sensor, "B7");
+        assert_eq!(
+            collapse_check_records(&records, 5, strategy),
+            Err(violations[0].clone())
+        );
+    }
+    assert_eq!(
+        collapse_check_records(&records, 5, Strategy::Resort)
+            .unwrap_err()
+            .to_string(),
+        "Sensor B7 at 2024-03-01T06:14:00Z: Imminent Failure: Minimum is too big to reach the required sum. Index: 14, Element: 127, Window min: 95, Window max: 182"
+    );
+}
+
+#[test]
+fn first_violation_is_the_first_logged() {
+    use namt_preventative_collapse::{
+        collapse_check_records, collapse_scan_records, parse_csv, CsvFormat, Strategy,
+    };
+    //C2 fails on its 3rd reading, logged before B7 fails on its own 3rd reading.
+    let text = "1,B7,1\n1,C2,1\n2,C2,1\n3,B7,1\n4,C2,7\n5,B7,1\n6,B7,4\n";
+    let records = parse_csv::<u32>(text, &CsvFormat::default()).unwrap();
+    let violation = collapse_check_records(&records, 2, Strategy::Resort).unwrap_err();
+    assert_eq!((violation.record, violation.sensor.as_str()), (4, "C2"));
+    assert_eq!(violation.error.index(), 2);
+    let records: Vec<usize> = collapse_scan_records(&records, 2, Strategy::Resort)
+        .iter()
+        .map(|violation| violation.record)
+        .collect();
+    assert_eq!(records, vec![4, 5, 6]);
+}
+
+#[test]
+fn header_detection() {
+    use namt_preventative_collapse::{parse_csv, CsvFormat, Header, ParseError, ParseErrorKind};
+    let rows = "# Logger 3\n\n06:00,B7,35\n06:01,B7,20\n";
+    let with_header = format!("time,sensor,kpa\n{}", rows);
+    let values = |text: &str, header| {
+        let format = CsvFormat {
+            header,
+            ..CsvFormat::default()
+        };
+        parse_csv::<u128>(text, &format).map(|records| {
+            records
+                .iter()
+                .map(|record| record.value)
+                .collect::<Vec<_>>()
+        })
+    };
+    assert_eq!(values(rows, Header::Auto), Ok(vec![35, 20]));
+    assert_eq!(values(&with_header, Header::Auto), Ok(vec![35, 20]));
+    assert_eq!(values(&with_header, Header::Present), Ok(vec![35, 20]));
+    assert_eq!(values(rows, Header::Present), Ok(vec![20]));
+    assert_eq!(
+        values(&with_header, Header::Absent),
+        Err(ParseError {
+            line: 1,
+            column: 13,
+            kind: ParseErrorKind::InvalidDigit
+        })
+    );
+    assert_eq!(values("", Header::Auto), Ok(vec![]));
+    assert_eq!(values("time,sensor,kpa\n", Header::Auto), Ok(vec![]));
+}
+
+#[test]
+fn column_selection_and_delimiter() {
+    use namt_preventative_collapse::{
+        parse_csv, Column, CsvFormat, Header, ParseError, ParseErrorKind, Record,
+    };
+    let expected = vec![
+        Record {
+            timestamp: "06:00".to_string(),
+            sensor: "B7".to_string(),
+            value: 35,
+        },
+        Record {
+            timestamp: "06:01".to_string(),
+            sensor: "C2".to_string(),
+            value: 0x14,
+        },
+    ];
+    let by_name = CsvFormat {
+        delimiter: ';',
+        timestamp: Column::Name("logged_at".to_string()),
+        sensor: Column::Name("id".to_string()),
+        value: Column::Name("kpa".to_string()),
+        ..CsvFormat::default()
+    };
+    let text = "kpa; id ;battery;logged_at\n35;B7;98;06:00\n0x14 ; C2;97;06:01\n";
+    assert_eq!(parse_csv::<u16>(text, &by_name), Ok(expected.clone()));
+
+    let by_index = CsvFormat {
+        delimiter: '\t',
+        header: Header::Absent,
+        timestamp: Column::Index(3),
+        sensor: Column::Index(1),
+        value: Column::Index(0),
+    };
+    let text = "35\tB7\t98\t06:00\r\n0x14\tC2\t97\t06:01\r\n";
+    assert_eq!(parse_csv::<u16>(text, &by_index), Ok(expected));
+
+    let unknown = CsvFormat {
+        value: Column::Name("pressure".to_string()),
+        ..CsvFormat::default()
+    };
+    assert_eq!(
+        parse_csv::<u16>("\ntime,sensor,kpa\n06:00,B7,35\n", &unknown),
+        Err(ParseError {
+            line: 2,
+            column: 1,
+            kind: ParseErrorKind::UnknownColumn
+        })
+    );
+}
+
+#[test]
+fn quoted_fields() {
+    use namt_preventative_collapse::{parse_csv, CsvFormat, ParseError, ParseErrorKind};
+    let text = "\"2024-03-01 06:00\",\"Shaft B, \"\"north\"\"\",\"35\"\n";
+    let records = parse_csv::<u8>(text, &CsvFormat::default()).unwrap();
+    assert_eq!(records[0].timestamp, "2024-03-01 06:00");
+    assert_eq!(records[0].sensor, "Shaft B, \"north\"");
+    assert_eq!(records[0].value, 35);
+    assert_eq!(
+        parse_csv::<u8>("06:00, \"B7,35\n", &CsvFormat::default()),
+        Err(ParseError {
+            line: 1,
+            column: 8,
+            kind: ParseErrorKind::UnterminatedQuote
+        })
+    );
+}
+
+#[test]
+fn malformed_rows_report_line_and_column() {
+    use namt_preventative_collapse::{parse_csv, CsvFormat, ParseError, ParseErrorKind};
+    let error = |line, column, kind| ParseError { line, column, kind };
+    let parse = |text| parse_csv::<u8>(text, &CsvFormat::default());
+    assert_eq!(
+        parse("06:00,B7,35\n06:01,B7\n"),
+        Err(error(2, 9, ParseErrorKind::MissingField))
+    );
+    assert_eq!(
+        parse("06:00,B7,35\n06:01,B7,  3x5\n"),
+        Err(error(2, 13, ParseErrorKind::InvalidDigit))
+    );
+    assert_eq!(
+        parse("06:00,B7,35\n06:01,B7,256\n"),
+        Err(error(2, 10, ParseErrorKind::Overflow))
+    );
+    assert_eq!(
+        parse("06:00,B7,35\n06:01,B7, \n"),
+        Err(error(2, 11, ParseErrorKind::NoDigits))
+    );
+}
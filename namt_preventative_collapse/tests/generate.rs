@@ -0,0 +1,201 @@
+#[test]
+fn ground_truth_matches_scan() {
+    use namt_preventative_collapse::{collapse_scan, Generator, Strategy};
+    for period in [3, 4, 5, 7, 25, 100] {
+        for seed in 0..8 {
+            let generator = Generator {
+                seed,
+                random_anomalies: 12,
+                ..Generator::new(period, 40 * period)
+            };
+            let synthetic = generator.generate();
+            assert_eq!(synthetic.readings.len(), 40 * period);
+            assert!(!synthetic.anomalies.is_empty());
+            assert_eq!(
+                collapse_scan(&synthetic.readings, period),
+                synthetic.anomalies,
+                "Period {}, seed {}",
+                period,
+                seed
+            );
+            for strategy in [Strategy::Hashed, Strategy::Incremental] {
+                assert_eq!(
+                    strategy.collapse_scan(&synthetic.readings, period),
+                    synthetic.anomalies
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn valid_series_stay_bounded() {
+    use namt_preventative_collapse::{collapse_check, Generator};
+    let generator = Generator {
+        max_reading: 1000,
+        ..Generator::new(100, 100_000)
+    };
+    let synthetic = generator.generate();
+    assert!(synthetic.anomalies.is_empty());
+    assert_eq!(collapse_check(&synthetic.readings, 100), Ok(()));
+    assert!(synthetic.readings.iter().all(|reading| *reading <= 1000));
+    //Not just zeros and copies.
+    let mut distinct = synthetic.readings.clone();
+    distinct.sort_unstable();
+    distinct.dedup();
+    assert!(distinct.len() > 100);
+}
+
+#[test]
+fn chosen_anomalies_are_injected() {
+    use namt_preventative_collapse::{CollapseError, Generator, Injection};
+    let generator = Generator {
+        anomalies: vec![
+            (5, Injection::TooSmall),
+            (10, Injection::TooSmall),
+            (15, Injection::NoPair),
+            (20, Injection::TooLarge),
+            (25, Injection::TooSmall),
+        ],
+        ..Generator::new(4, 30)
+    };
+    let synthetic = generator.generate();
+    let kinds: Vec<_> = synthetic
+        .anomalies
+        .iter()
+        .map(|error| (error.index(), error.kind()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (5, "MinTooBig"),
+            (10, "MinTooBig"),
+            (15, "NoPairFound"),
+            (20, "MaxTooSmall"),
+            (25, "MinTooBig")
+        ]
+    );
+    assert_eq!(synthetic.readings[5], 0);
+    assert!(matches!(
+        synthetic.anomalies[3],
+        CollapseError::MaxTooSmall { target, max, .. } if target > 2 * max
+    ));
+    assert_eq!(
+        namt_preventative_collapse::collapse_scan(&synthetic.readings, 4),
+        synthetic.anomalies
+    );
+}
+
+#[test]
+fn same_seed_same_series() {
+    use namt_preventative_collapse::Generator;
+    let generator = Generator {
+        random_anomalies: 5,
+        ..Generator::new(10, 1000)
+    };
+    assert_eq!(generator.generate(), generator.generate());
+    let other = Generator {
+        seed: 1,
+        ..generator.clone()
+    };
+    assert_ne!(generator.generate().readings, other.generate().readings);
+}
+
+#[test]
+fn crowded_random_anomalies_are_left_out() {
+    use namt_preventative_collapse::{collapse_scan, Generator};
+    let generator = Generator {
+        random_anomalies: 1000,
+        ..Generator::new(10, 200)
+    };
+    let synthetic = generator.generate();
+    //At most one every period + 1 readings, after the first period.
+    assert!(synthetic.anomalies.len() <= 190 / 11 + 1);
+    assert_eq!(collapse_scan(&synthetic.readings, 10), synthetic.anomalies);
+}
+
+#[test]
+fn writes_data_and_ground_truth() {
+    use namt_preventative_collapse::{parse_readings, Generator, Injection};
+    let generator = Generator {
+        anomalies: vec![(3, Injection::TooLarge)],
+        ..Generator::new(3, 6)
+    };
+    let synthetic = generator.generate();
+    let mut data = vec![];
+    synthetic.write_readings(&mut data).unwrap();
+    assert_eq!(
+        parse_readings::<u128>(&String::from_utf8(data).unwrap()).unwrap(),
+        synthetic.readings
+    );
+    let mut truth = vec![];
+    synthetic.write_anomalies(&mut truth).unwrap();
+    let error = &synthetic.anomalies[0];
+    let (min, max) = error.window().unwrap();
+    assert_eq!(
+        String::from_utf8(truth).unwrap(),
+        format!(
+            "index,kind,target,min,max\n3,MaxTooSmall,{},{},{}\n",
+            error.target(),
+            min,
+            max
+        )
+    );
+}
+
+#[test]
+#[should_panic]
+fn anomalies_too_close_panic() {
+    use namt_preventative_collapse::{Generator, Injection};
+    Generator {
+        anomalies: vec![(10, Injection::NoPair), (13, Injection::TooLarge)],
+        ..Generator::new(3, 20)
+    }
+    .generate();
+}
+
+#[test]
+#[should_panic]
+fn unchecked_anomaly_panics() {
+    use namt_preventative_collapse::{Generator, Injection};
+    Generator {
+        anomalies: vec![(2, Injection::NoPair)],
+        ..Generator::new(3, 20)
+    }
+    .generate();
+}
+
+#[test]
+fn spikes_fit_at_the_largest_max_reading() {
+    use namt_preventative_collapse::{collapse_scan, Generator, Injection};
+    for period in [3, 5, 25] {
+        for seed in 0..50 {
+            let generator = Generator {
+                seed,
+                max_reading: Generator::MAX_READING,
+                anomalies: vec![
+                    (period, Injection::TooLarge),
+                    (4 * period, Injection::TooLarge),
+                ],
+                random_anomalies: 20,
+                ..Generator::new(period, 80 * period)
+            };
+            let synthetic = generator.generate();
+            assert_eq!(
+                collapse_scan(&synthetic.readings, period),
+                synthetic.anomalies
+            );
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn max_reading_past_the_limit_panics() {
+    use namt_preventative_collapse::Generator;
+    let generator = Generator {
+        max_reading: Generator::MAX_READING + 1,
+        ..Generator::new(5, 10)
+    };
+    generator.generate();
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use common::read;
+
+#[test]
+fn hashed_early_warning_system() {
+    use namt_preventative_collapse::{collapse_check, collapse_check_hashed};
+    for vec in [
+        vec![2, 1, 2, 7],
+        vec![6, 4, 5, 7],
+        vec![2, 1, 3, 5, 4, 7, 13],
+        vec![1, 2, 3, 4],
+        vec![3, 3, 3, 6, 0, 6],
+    ] {
+        assert_eq!(collapse_check_hashed(&vec, 3), collapse_check(&vec, 3));
+    }
+}
+
+#[test]
+fn hashed_matches_collapse_check_on_test_inputs() {
+    use namt_preventative_collapse::{collapse_check, collapse_check_hashed};
+    let numbers = read("../test_inputs/mini_mine.txt");
+    assert_eq!(
+        collapse_check_hashed(&numbers, 5),
+        collapse_check(&numbers, 5)
+    );
+
+    let numbers = read("../test_inputs/mega_mine.txt");
+    for period in [25, 100, 1000, 10000] {
+        let result = collapse_check_hashed(&numbers, period);
+        assert_eq!(result, collapse_check(&numbers, period));
+    }
+}
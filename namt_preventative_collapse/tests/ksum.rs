@@ -0,0 +1,145 @@
+mod common;
+
+use common::pseudo_random_signed_series;
+
+///Every way of picking `k` positions of `window`, ascending, distinct or not.
+fn picks(len: usize, k: usize, distinct: bool) -> Vec<Vec<usize>> {
+    match k {
+        0 => vec![vec![]],
+        k => picks(len, k - 1, distinct)
+            .into_iter()
+            .flat_map(|pick| {
+                let start = match (pick.last(), distinct) {
+                    (None, _) => 0,
+                    (Some(last), true) => last + 1,
+                    (Some(last), false) => *last,
+                };
+                (start..len).map(move |next| [pick.clone(), vec![next]].concat())
+            })
+            .collect(),
+    }
+}
+
+///Brute force oracle in `i128`. Indices whose reading no `k` of the preceding `period` readings sum to.
+fn oracle(vector: &[i128], period: usize, ksum: namt_preventative_collapse::KSum) -> Vec<usize> {
+    let picks = picks(period, ksum.k, ksum.distinct);
+    (period..vector.len())
+        .filter(|c| {
+            let (window, target) = (&vector[c - period..*c], vector[*c]);
+            let min = *window.iter().min().unwrap();
+            let max = *window.iter().max().unwrap();
+            //The early warnings come first.
+            ksum.k as i128 * max < target
+                || ksum.k as i128 * min > target
+                || !picks
+                    .iter()
+                    .any(|pick| pick.iter().map(|p| window[*p]).sum::<i128>() == target)
+        })
+        .collect()
+}
+
+fn indices<T>(anomalies: Vec<namt_preventative_collapse::CollapseError<T>>) -> Vec<usize>
+where
+    T: namt_preventative_collapse::Reading,
+{
+    anomalies.iter().map(|error| error.index()).collect()
+}
+
+///Every model, on `series` narrowed to `T`, against the oracle.
+fn assert_matches_oracle<T>(series: &[i128], period: usize)
+where
+    T: namt_preventative_collapse::Reading + TryFrom<i128>,
+    T::Error: std::fmt::Debug,
+{
+    use namt_preventative_collapse::KSum;
+    let readings: Vec<T> = series.iter().map(|x| T::try_from(*x).unwrap()).collect();
+    for k in 1..=3 {
+        for distinct in [false, true] {
+            let ksum = KSum { k, distinct };
+            assert_eq!(
+                indices(ksum.collapse_scan(&readings, period)),
+                oracle(series, period, ksum),
+                "{:?} {:?} period {}",
+                ksum,
+                series,
+                period
+            );
+        }
+    }
+}
+
+#[test]
+fn ksum_matches_oracle() {
+    for seed in 0..50 {
+        let series = pseudo_random_signed_series(seed, 40, 24, false);
+        let signed = pseudo_random_signed_series(seed, 40, 24, true);
+        for period in 1..6 {
+            assert_matches_oracle::<u32>(&series, period);
+            assert_matches_oracle::<i64>(&signed, period);
+        }
+    }
+}
+
+#[test]
+fn ksum_never_overflows_narrow_types() {
+    for seed in 0..50 {
+        let series = pseudo_random_signed_series(seed, 40, 256, false);
+        let signed = pseudo_random_signed_series(seed, 40, 256, true);
+        for period in 1..5 {
+            assert_matches_oracle::<u8>(&series, period);
+            assert_matches_oracle::<i8>(&signed, period);
+        }
+    }
+    //-128 = -128 + -127 + 127, where -128 - 127 is past i8::MIN.
+    assert_matches_oracle::<i8>(&[-127, 127, -128, -128, 120, 0, -1, -128], 3);
+}
+
+#[test]
+fn ksum_matches_oracle_on_mega_mine() {
+    use namt_preventative_collapse::parse_readings;
+    use std::fs;
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
+    let wide: Vec<i128> = numbers[..600].iter().map(|x| *x as i128).collect();
+    assert_matches_oracle::<u128>(&wide, 25);
+}
+
+#[test]
+fn pairs_of_different_positions_are_the_strict_rule() {
+    use namt_preventative_collapse::parse_readings;
+    use namt_preventative_collapse::{KSum, PairRule};
+    use std::fs;
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
+    let strict = PairRule::Strict {
+        distinct_values: false,
+    };
+    let ksum = KSum {
+        k: 2,
+        distinct: true,
+    };
+    assert_eq!(
+        ksum.collapse_scan(&numbers, 100),
+        strict.collapse_scan(&numbers, 100)
+    );
+}
+
+#[test]
+fn larger_k_through_the_strategy() {
+    use namt_preventative_collapse::{collapse_check_with, KSumWindow};
+    let vec: Vec<u16> = vec![1, 2, 3, 4, 5, 14];
+    assert!(collapse_check_with::<KSumWindow<4, true, u16>>(&vec, 5).is_ok());
+    assert!(collapse_check_with::<KSumWindow<3, true, u16>>(&vec, 5).is_err());
+    assert!(collapse_check_with::<KSumWindow<5, true, u16>>(&vec, 5).is_err());
+}
+
+#[test]
+#[should_panic]
+fn ksum_rejects_unsupported_k() {
+    use namt_preventative_collapse::KSum;
+    let _ = KSum {
+        k: 4,
+        distinct: false,
+    }
+    .collapse_check(&[1, 2, 3, 4], 3);
+}
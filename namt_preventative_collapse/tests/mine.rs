@@ -0,0 +1,118 @@
+mod common;
+
+use common::read_text;
+
+#[test]
+fn mine_monitor_matches_collapse_scan_records() {
+    use namt_preventative_collapse::{
+        collapse_scan_records, parse_csv, CsvFormat, MineMonitor, Strategy,
+    };
+    let records = parse_csv::<u128>(
+        &read_text("../test_inputs/mine_log.csv"),
+        &CsvFormat::default(),
+    )
+    .unwrap();
+    let mut mine = MineMonitor::new(5);
+    let mut failing = vec![];
+    for (c, record) in records.iter().enumerate() {
+        if let Err(error) = mine.push(&record.sensor, record.value) {
+            failing.push((c, error));
+        }
+    }
+    let expected: Vec<_> = collapse_scan_records(&records, 5, Strategy::Resort)
+        .into_iter()
+        .map(|violation| (violation.record, violation.error))
+        .collect();
+    assert_eq!(failing, expected);
+    assert_eq!(mine.len(), 2);
+    assert_eq!(mine.sensor("B7").unwrap().len(), 20);
+    //B7 failed once, at 06:14, and has been safe since.
+    assert!(mine.status().is_safe());
+    assert_eq!(mine.status().safe, 2);
+}
+
+#[test]
+fn sensors_keep_independent_windows_and_periods() {
+    use namt_preventative_collapse::{CollapseError, MineMonitor, Verdict};
+    let mut mine = MineMonitor::new(2);
+    mine.add_sensor("deep", 4);
+    //Interleaved, the two series would be checked against each other's readings.
+    for reading in [10, 20] {
+        assert_eq!(mine.push("shallow", reading), Ok(Verdict::Warming));
+        assert_eq!(mine.push("deep", reading + 1), Ok(Verdict::Warming));
+    }
+    assert_eq!(mine.push("shallow", 30), Ok(Verdict::Safe));
+    assert_eq!(mine.push("deep", 31), Ok(Verdict::Warming));
+    assert_eq!(mine.push("deep", 41), Ok(Verdict::Warming));
+    //52 - 21 = 31, all from the deep window.
+    assert_eq!(mine.push("deep", 52), Ok(Verdict::Safe));
+    assert_eq!(
+        mine.push("shallow", 15),
+        Err(CollapseError::MinTooBig {
+            index: 3,
+            target: 15,
+            min: 20,
+            max: 30
+        })
+    );
+    assert_eq!(mine.sensor("deep").unwrap().period(), 4);
+    assert_eq!(mine.sensor("shallow").unwrap().period(), 2);
+    assert_eq!(mine.default_period(), 2);
+}
+
+#[test]
+fn status_aggregates_latest_verdicts() {
+    use namt_preventative_collapse::{MineMonitor, MineStatus, Verdict};
+    let mut mine = MineMonitor::new(2);
+    assert!(mine.is_empty());
+    assert_eq!(mine.status(), MineStatus::default());
+    assert!(mine.status().is_safe());
+
+    mine.add_sensor("idle", 3);
+    for sensor in ["a", "b", "c"] {
+        mine.push(sensor, 1).unwrap();
+        mine.push(sensor, 2).unwrap();
+    }
+    mine.push("a", 3).unwrap();
+    mine.push("b", 9).unwrap_err();
+    mine.push("c", 9).unwrap_err();
+    let status = mine.status();
+    assert_eq!(status.warming, 1);
+    assert_eq!(status.safe, 1);
+    assert_eq!(status.failing, vec!["b".to_string(), "c".to_string()]);
+    assert_eq!(status.sensors(), 4);
+    assert!(!status.is_safe());
+
+    //A safe reading clears the sensor.
+    assert_eq!(mine.push("b", 11), Ok(Verdict::Safe));
+    assert_eq!(mine.status().failing, vec!["c".to_string()]);
+    let verdicts: Vec<_> = mine
+        .verdicts()
+        .map(|(sensor, verdict)| (sensor, verdict.is_ok()))
+        .collect();
+    assert_eq!(
+        verdicts,
+        vec![("a", true), ("b", true), ("c", false), ("idle", true)]
+    );
+    assert_eq!(mine.verdict("idle"), Some(&Ok(Verdict::Warming)));
+    assert_eq!(mine.verdict("missing"), None);
+}
+
+#[test]
+fn with_strategy_and_narrow_readings() {
+    use namt_preventative_collapse::{HashedWindow, MineMonitor, ResortWindow};
+    let mut hashed = MineMonitor::<HashedWindow<i16>>::with_strategy(3);
+    let mut resort = MineMonitor::<ResortWindow<i16>>::with_strategy(3);
+    for (c, reading) in [-3i16, 5, -1, 2, 7, -9, 4, 4, 100].into_iter().enumerate() {
+        let sensor = ["x", "y"][c % 2];
+        assert_eq!(hashed.push(sensor, reading), resort.push(sensor, reading));
+    }
+    assert_eq!(hashed.status(), resort.status());
+}
+
+#[test]
+#[should_panic]
+fn zero_default_period_panics() {
+    use namt_preventative_collapse::MineMonitor;
+    MineMonitor::new(0);
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use common::read;
+
+#[test]
+fn monitor_matches_collapse_check_on_test_inputs() {
+    use namt_preventative_collapse::{collapse_check, collapse_scan, CollapseMonitor, Verdict};
+    for (path, period) in [
+        ("../test_inputs/mini_mine.txt", 5),
+        ("../test_inputs/mega_mine.txt", 100),
+        ("../test_inputs/challenge_input.txt", 100),
+    ] {
+        let numbers = read(path);
+        let mut monitor = CollapseMonitor::new(period);
+        let mut anomalies = vec![];
+        for (c, reading) in numbers.iter().enumerate() {
+            match monitor.push(*reading) {
+                Ok(Verdict::Warming) => assert!(c < period),
+                Ok(Verdict::Safe) => assert!(c >= period),
+                Err(error) => anomalies.push(error),
+            }
+        }
+        assert_eq!(monitor.len(), numbers.len());
+        assert_eq!(anomalies, collapse_scan(&numbers, period));
+        assert_eq!(collapse_check(&numbers, period), Err(anomalies[0].clone()));
+    }
+}
+
+#[test]
+fn monitor_early_warnings() {
+    use namt_preventative_collapse::{CollapseError, CollapseMonitor};
+    let mut monitor = CollapseMonitor::new(3);
+    for reading in [2, 1, 2] {
+        monitor.push(reading).unwrap();
+    }
+    assert_eq!(
+        monitor.push(7),
+        Err(CollapseError::MaxTooSmall {
+            index: 3,
+            target: 7,
+            min: 1,
+            max: 2
+        })
+    );
+    //The failing reading still slides into the window.
+    assert_eq!(
+        monitor.push(20),
+        Err(CollapseError::MaxTooSmall {
+            index: 4,
+            target: 20,
+            min: 1,
+            max: 7
+        })
+    );
+}
+
+#[test]
+#[should_panic]
+fn monitor_rejects_empty_period() {
+    use namt_preventative_collapse::CollapseMonitor;
+    CollapseMonitor::new(0);
+}
+
+///Saves and restores `checkpoint` the way a collector would, through JSON when serde is available.
+fn restart(
+    checkpoint: namt_preventative_collapse::Checkpoint,
+) -> namt_preventative_collapse::Checkpoint {
+    #[cfg(feature = "serde")]
+    let checkpoint = serde_json::from_str(&serde_json::to_string(&checkpoint).unwrap()).unwrap();
+    checkpoint
+}
+
+#[test]
+fn resumed_monitor_matches_uninterrupted_run() {
+    use namt_preventative_collapse::{CollapseMonitor, HashedWindow, IncrementalWindow};
+    let numbers = read("../test_inputs/mega_mine.txt");
+    let period = 100;
+    let mut monitor = CollapseMonitor::new(period);
+    let uninterrupted: Vec<_> = numbers
+        .iter()
+        .map(|&reading| monitor.push(reading))
+        .collect();
+    assert!(monitor.anomalies() > 0);
+    let first = uninterrupted.iter().position(Result::is_err).unwrap();
+
+    for split in [
+        0,
+        1,
+        period - 1,
+        period,
+        period + 1,
+        first,
+        first + 1,
+        numbers.len(),
+    ] {
+        let mut before = CollapseMonitor::new(period);
+        let mut results: Vec<_> = numbers[..split]
+            .iter()
+            .map(|&reading| before.push(reading))
+            .collect();
+        let checkpoint = restart(before.checkpoint());
+        assert_eq!(checkpoint.index, split);
+
+        let mut after = CollapseMonitor::resume(checkpoint.clone()).unwrap();
+        results.extend(numbers[split..].iter().map(|&reading| after.push(reading)));
+        assert_eq!(results, uninterrupted, "Split at {}", split);
+        assert_eq!(after.checkpoint(), monitor.checkpoint());
+
+        //The resumed monitor may search the window differently.
+        let mut hashed =
+            CollapseMonitor::<HashedWindow>::resume_with_strategy(checkpoint.clone()).unwrap();
+        let mut incremental =
+            CollapseMonitor::<IncrementalWindow>::resume_with_strategy(checkpoint).unwrap();
+        for (c, &reading) in numbers.iter().enumerate().skip(split) {
+            assert_eq!(hashed.push(reading), uninterrupted[c]);
+            assert_eq!(incremental.push(reading), uninterrupted[c]);
+        }
+        assert_eq!(hashed.anomalies(), monitor.anomalies());
+    }
+}
+
+#[test]
+fn inconsistent_checkpoints_are_rejected() {
+    use namt_preventative_collapse::{Checkpoint, CollapseMonitor};
+    let valid = Checkpoint {
+        period: 3,
+        index: 5,
+        anomalies: 2,
+        window: vec![1, 2, 3],
+    };
+    assert!(CollapseMonitor::resume(valid.clone()).is_some());
+    for checkpoint in [
+        Checkpoint {
+            period: 0,
+            index: 0,
+            anomalies: 0,
+            window: vec![],
+        },
+        Checkpoint {
+            window: vec![1, 2],
+            ..valid.clone()
+        },
+        Checkpoint {
+            index: 2,
+            ..valid.clone()
+        },
+        Checkpoint {
+            anomalies: 3,
+            ..valid.clone()
+        },
+    ] {
+        assert!(CollapseMonitor::resume(checkpoint).is_none());
+    }
+    //Still warming up.
+    let mut monitor = CollapseMonitor::resume(Checkpoint {
+        period: 3,
+        index: 2,
+        anomalies: 0,
+        window: vec![1, 2],
+    })
+    .unwrap();
+    assert!(monitor.push(3).is_ok());
+    assert!(monitor.push(100).is_err());
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use common::{pseudo_random_series, read};
+use namt_preventative_collapse::{CollapseError, CollapseObserver};
+
+///Every event, in order.
+#[derive(Debug, Default, PartialEq)]
+struct Recorder {
+    anomalies: Vec<CollapseError>,
+    advances: Vec<(usize, u128, u128)>,
+}
+
+impl CollapseObserver for Recorder {
+    fn on_anomaly(&mut self, error: &CollapseError) {
+        self.anomalies.push(error.clone());
+    }
+
+    fn on_window_advance(&mut self, index: usize, expired: u128, incoming: u128) {
+        self.advances.push((index, expired, incoming));
+    }
+}
+
+#[test]
+fn observed_matches_check_and_scan() {
+    use namt_preventative_collapse::{
+        collapse_check, collapse_check_observed, collapse_scan, collapse_scan_observed,
+    };
+    for (path, period) in [
+        ("../test_inputs/mini_mine.txt", 5),
+        ("../test_inputs/challenge_input.txt", 100),
+    ] {
+        let numbers = read(path);
+        let mut recorder = Recorder::default();
+        let anomalies = collapse_scan_observed(&numbers, period, &mut recorder);
+        assert_eq!(anomalies, collapse_scan(&numbers, period));
+        assert_eq!(recorder.anomalies, anomalies);
+        //Failing readings enter the window too.
+        assert_eq!(recorder.advances.len(), numbers.len() - period);
+        for &(index, expired, incoming) in &recorder.advances {
+            assert_eq!(
+                (expired, incoming),
+                (numbers[index - period], numbers[index])
+            );
+        }
+
+        let mut recorder = Recorder::default();
+        let result = collapse_check_observed(&numbers, period, &mut recorder);
+        assert_eq!(result, collapse_check(&numbers, period));
+        let first = result.unwrap_err();
+        assert_eq!(recorder.anomalies, vec![first.clone()]);
+        //The window stops before the failing reading.
+        assert_eq!(recorder.advances.len(), first.index() - period);
+    }
+}
+
+#[test]
+fn closures_observe_anomalies() {
+    use namt_preventative_collapse::{collapse_scan_observed, CollapseMonitor};
+    let mut indices = vec![];
+    collapse_scan_observed(&[1, 1, 1, 4, 2, 9], 3, &mut |error: &CollapseError| {
+        indices.push(error.index())
+    });
+    assert_eq!(indices, vec![3, 5]);
+
+    //Narrow readings, through a monitor.
+    let mut monitor =
+        CollapseMonitor::<namt_preventative_collapse::ResortWindow<i8>>::with_strategy(2);
+    let mut targets = vec![];
+    for reading in [-3i8, 5, 2, 100] {
+        let _ = monitor.push_observed(reading, &mut |error: &CollapseError<i8>| {
+            targets.push(error.target())
+        });
+    }
+    assert_eq!(targets, vec![100]);
+    assert!(monitor.push_observed(7, &mut ()).is_err());
+}
+
+#[test]
+fn monitor_reports_the_same_events() {
+    use namt_preventative_collapse::{collapse_scan_observed, CollapseMonitor};
+    for seed in 0..20 {
+        let numbers = pseudo_random_series(seed, 60, 16);
+        for period in [1, 3, 7] {
+            let mut expected = Recorder::default();
+            collapse_scan_observed(&numbers, period, &mut expected);
+            let mut monitor = CollapseMonitor::new(period);
+            let mut recorder = Recorder::default();
+            for &reading in &numbers {
+                let _ = monitor.push_observed(reading, &mut recorder);
+            }
+            assert_eq!(recorder, expected);
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn log_observer_warns_on_anomalies() {
+    use namt_preventative_collapse::{collapse_scan_observed, LogObserver};
+    use std::sync::Mutex;
+
+    static RECORDS: Mutex<Vec<(log::Level, String)>> = Mutex::new(vec![]);
+    struct Capture;
+    impl log::Log for Capture {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+        fn log(&self, record: &log::Record) {
+            RECORDS
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+        fn flush(&self) {}
+    }
+    log::set_logger(&Capture).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    collapse_scan_observed(&[1, 1, 1, 4, 2], 3, &mut LogObserver);
+    let records = RECORDS.lock().unwrap();
+    assert_eq!(
+        *records,
+        vec![
+            (
+                log::Level::Warn,
+                "Imminent Failure: Maximum is too small to reach the required sum. Index: 3, Element: 4, Window min: 1, Window max: 1"
+                    .to_string()
+            ),
+            (
+                log::Level::Trace,
+                "Index: 3, Expired: 1, Incoming: 4".to_string()
+            ),
+            (
+                log::Level::Trace,
+                "Index: 4, Expired: 1, Incoming: 2".to_string()
+            ),
+        ]
+    );
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_observer_emits_events() {
+    use namt_preventative_collapse::{collapse_scan_observed, TracingObserver};
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata, Subscriber};
+
+    struct Capture(Arc<Mutex<Vec<Level>>>);
+    impl Subscriber for Capture {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            self.0.lock().unwrap().push(*event.metadata().level());
+        }
+        fn enter(&self, _span: &Id) {}
+        fn exit(&self, _span: &Id) {}
+    }
+    let levels = Arc::new(Mutex::new(vec![]));
+    tracing::subscriber::with_default(Capture(levels.clone()), || {
+        collapse_scan_observed(&[1, 1, 1, 4, 2], 3, &mut TracingObserver);
+    });
+    assert_eq!(
+        *levels.lock().unwrap(),
+        vec![Level::WARN, Level::TRACE, Level::TRACE]
+    );
+}
@@ -0,0 +1,122 @@
+const HALF: u128 = u128::MAX / 2;
+
+#[test]
+fn sums_reaching_u128_max_are_safe() {
+    use namt_preventative_collapse::{collapse_check, PairRule, Strategy};
+    //HALF + (HALF + 1) == u128::MAX, and twice the maximum overflows.
+    let vec = vec![HALF, HALF + 1, 5, u128::MAX];
+    assert_eq!(collapse_check(&vec, 3), Ok(()));
+    for strategy in [Strategy::Resort, Strategy::Hashed, Strategy::Incremental] {
+        assert_eq!(strategy.collapse_check(&vec, 3), Ok(()));
+    }
+    for distinct_values in [false, true] {
+        assert_eq!(
+            PairRule::Strict { distinct_values }.collapse_check(&vec, 3),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+fn minimum_past_half_of_u128_max_is_too_big() {
+    use namt_preventative_collapse::{collapse_check, CollapseError, PairRule, Strategy};
+    //Twice the minimum overflows, so no pair reaches u128::MAX - 7.
+    let vec = vec![HALF + 1, HALF + 2, HALF + 3, u128::MAX - 7];
+    let expected = Err(CollapseError::MinTooBig {
+        index: 3,
+        target: u128::MAX - 7,
+        min: HALF + 1,
+        max: HALF + 3,
+    });
+    assert_eq!(collapse_check(&vec, 3), expected);
+    for strategy in [Strategy::Resort, Strategy::Hashed, Strategy::Incremental] {
+        assert_eq!(strategy.collapse_check(&vec, 3), expected);
+    }
+    assert_eq!(
+        PairRule::Strict {
+            distinct_values: false
+        }
+        .collapse_check(&vec, 3),
+        expected
+    );
+}
+
+#[test]
+fn huge_window_small_target() {
+    use namt_preventative_collapse::{collapse_scan, PairRule, Strategy};
+    let vec = vec![u128::MAX, u128::MAX - 1, 3, 6, u128::MAX, 9, u128::MAX - 1];
+    let expected = collapse_scan(&vec, 3);
+    //6 is 3 + 3 and 9 is 6 + 3. u128::MAX is out of reach, and so is u128::MAX - 1 once u128::MAX leaves.
+    assert_eq!(
+        expected
+            .iter()
+            .map(|error| error.index())
+            .collect::<Vec<_>>(),
+        vec![4, 6]
+    );
+    for strategy in [Strategy::Hashed, Strategy::Incremental] {
+        assert_eq!(strategy.collapse_scan(&vec, 3), expected);
+    }
+    let strict = PairRule::Strict {
+        distinct_values: false,
+    }
+    .collapse_scan(&vec, 3);
+    assert_eq!(
+        strict.iter().map(|error| error.index()).collect::<Vec<_>>(),
+        vec![3, 4, 6] //6 is no longer 3 + 3.
+    );
+}
+
+#[test]
+fn experiments_agree_near_u128_max() {
+    use namt_preventative_collapse::bench_iterations::{
+        collapse_check_are_references_better, collapse_check_narrowing_search,
+        collapse_check_with_more_short_circuiting,
+    };
+    use namt_preventative_collapse::collapse_check;
+    //The early warnings double readings past u128::MAX / 2. `collapse_check_no_short_ciruit` skips them, so it is left out.
+    //`collapse_check_alloc_and_sort_on_each_loop` checks against every earlier reading, not just the window.
+    let experiments = [
+        collapse_check_are_references_better,
+        collapse_check_with_more_short_circuiting,
+    ];
+    let mut series = vec![
+        vec![HALF, HALF + 1, 5, u128::MAX],
+        vec![HALF + 1, HALF + 2, HALF + 3, u128::MAX - 7],
+        vec![u128::MAX, u128::MAX - 1, 3, 6, u128::MAX, 9, u128::MAX - 1],
+    ];
+    //Readings around HALF and u128::MAX, with a few small ones, so sums land on both sides of the boundary.
+    let mut state: u64 = 7;
+    for _ in 0..200 {
+        let vec = (0..12)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let offset = u128::from((state >> 33) % 4);
+                match (state >> 40) % 4 {
+                    0 => u128::MAX - offset,
+                    1 => HALF - offset,
+                    2 => HALF + 1 + offset,
+                    _ => offset,
+                }
+            })
+            .collect();
+        series.push(vec);
+    }
+    for vec in &series {
+        for period in 2..5 {
+            let expected = collapse_check(vec, period);
+            for experiment in experiments {
+                assert_eq!(experiment(vec, period), expected, "{:?}", vec);
+            }
+        }
+    }
+    //Narrowing stops at half of the target and misses differences, so it only has to agree where doubling overflows.
+    for vec in &series[..2] {
+        assert_eq!(
+            collapse_check_narrowing_search(vec, 3),
+            collapse_check(vec, 3)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "rayon")]
+
+mod common;
+
+use common::{pseudo_random_series, pseudo_random_signed_series, read};
+
+#[test]
+fn parallel_matches_serial_on_test_inputs() {
+    use namt_preventative_collapse::{
+        collapse_check, collapse_check_parallel, collapse_scan, collapse_scan_parallel,
+    };
+    for (path, period) in [
+        ("../test_inputs/mini_mine.txt", 5),
+        ("../test_inputs/mega_mine.txt", 100),
+        ("../test_inputs/challenge_input.txt", 100),
+    ] {
+        let numbers = read(path);
+        assert_eq!(
+            collapse_check_parallel(&numbers, period),
+            collapse_check(&numbers, period)
+        );
+        assert_eq!(
+            collapse_scan_parallel(&numbers, period),
+            collapse_scan(&numbers, period)
+        );
+    }
+}
+
+#[test]
+fn parallel_matches_serial_on_generated_series() {
+    use namt_preventative_collapse::{
+        collapse_check, collapse_check_parallel, collapse_check_parallel_with, collapse_scan,
+        collapse_scan_parallel, collapse_scan_parallel_with, HashedWindow,
+    };
+    for (seed, len, modulus, period) in [
+        (1, 100_000, 1_000, 25),
+        (2, 50_000, 100_000, 50),
+        (3, 50_000, 10, 3),
+        (4, 10_000, 1 << 20, 300),
+    ] {
+        let numbers = pseudo_random_series(seed, len, modulus);
+        let anomalies = collapse_scan(&numbers, period);
+        assert!(!anomalies.is_empty());
+        assert_eq!(collapse_scan_parallel(&numbers, period), anomalies);
+        assert_eq!(
+            collapse_scan_parallel_with::<HashedWindow>(&numbers, period),
+            anomalies
+        );
+        assert_eq!(
+            collapse_check_parallel(&numbers, period),
+            collapse_check(&numbers, period)
+        );
+        //Only the last chunk fails.
+        let safe: Vec<u128> = (0..len).map(|c| (c % 3) as u128).collect();
+        let mut late = safe.clone();
+        late[len - 1] = u128::MAX;
+        assert_eq!(
+            collapse_check_parallel_with::<HashedWindow>(&safe, period),
+            Ok(())
+        );
+        assert_eq!(
+            collapse_check_parallel(&late, period),
+            collapse_check(&late, period)
+        );
+        assert_eq!(
+            collapse_check_parallel(&late, period).unwrap_err().index(),
+            len - 1
+        );
+    }
+}
+
+#[test]
+fn parallel_matches_serial_on_other_reading_types() {
+    use namt_preventative_collapse::{
+        collapse_check, collapse_check_parallel, collapse_scan, collapse_scan_parallel,
+    };
+    let signed: Vec<i64> = pseudo_random_signed_series(5, 50_000, 1_000, true)
+        .into_iter()
+        .map(|reading| reading as i64)
+        .collect();
+    let anomalies = collapse_scan(&signed, 25);
+    assert!(!anomalies.is_empty());
+    assert_eq!(collapse_scan_parallel(&signed, 25), anomalies);
+    assert_eq!(
+        collapse_check_parallel(&signed, 25),
+        collapse_check(&signed, 25)
+    );
+    let unsigned: Vec<u64> = (1..=10_000).collect();
+    assert_eq!(
+        collapse_check_parallel(&unsigned, 25),
+        collapse_check(&unsigned, 25)
+    );
+}
+
+#[test]
+fn short_inputs_are_safe() {
+    use namt_preventative_collapse::{collapse_check_parallel, collapse_scan_parallel};
+    assert_eq!(collapse_check_parallel::<u128>(&[], 5), Ok(()));
+    assert_eq!(collapse_scan_parallel(&[1, 2, 3, 4, 5], 5), vec![]);
+    assert_eq!(collapse_scan_parallel(&[1, 2, 3, 4, 5, 50], 5).len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn zero_period_panics() {
+    use namt_preventative_collapse::collapse_scan_parallel;
+    collapse_scan_parallel(&[1, 2, 3], 0);
+}
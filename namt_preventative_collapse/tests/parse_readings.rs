@@ -0,0 +1,110 @@
+#[test]
+fn parses_the_test_inputs() {
+    use namt_preventative_collapse::parse_readings;
+    use std::fs;
+    let numbers = fs::read_to_string("../test_inputs/mini_mine.txt").unwrap();
+    assert_eq!(
+        parse_readings::<u128>(&numbers),
+        Ok(vec![
+            35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309,
+            576
+        ])
+    );
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt").unwrap();
+    assert_eq!(
+        parse_readings::<u128>(&numbers).unwrap().len(),
+        numbers.lines().count()
+    );
+}
+
+#[test]
+fn skips_blank_lines_comments_and_whitespace() {
+    use namt_preventative_collapse::parse_readings;
+    let text = "\n# Header\n  35\t\r\n\t\n20 # inline\n   # indented comment\n15";
+    assert_eq!(parse_readings::<u32>(text), Ok(vec![35, 20, 15]));
+    assert_eq!(parse_readings::<u32>(""), Ok(vec![]));
+    assert_eq!(parse_readings::<u32>("# Only a comment\n\n"), Ok(vec![]));
+}
+
+#[test]
+fn separators_hex_and_signs() {
+    use namt_preventative_collapse::parse_readings;
+    assert_eq!(
+        parse_readings::<u128>("1_000\n0xFF\n0X1_0\n+7\n340282366920938463463374607431768211455"),
+        Ok(vec![1000, 255, 16, 7, u128::MAX])
+    );
+    assert_eq!(
+        parse_readings::<i64>("-1_000\n-0x10\n+0x10\n-9223372036854775808"),
+        Ok(vec![-1000, -16, 16, i64::MIN])
+    );
+}
+
+#[test]
+fn reports_line_and_column() {
+    use namt_preventative_collapse::{parse_readings, ParseError, ParseErrorKind};
+    let error = |line, column, kind| ParseError { line, column, kind };
+    assert_eq!(
+        parse_readings::<u128>("1\n2\n  3.5\n"),
+        Err(error(3, 4, ParseErrorKind::InvalidDigit))
+    );
+    assert_eq!(
+        parse_readings::<u128>("1\n12 34"),
+        Err(error(2, 3, ParseErrorKind::InvalidDigit))
+    );
+    assert_eq!(
+        parse_readings::<u128>("0xfg"),
+        Err(error(1, 4, ParseErrorKind::InvalidDigit))
+    );
+    //Columns count characters, not bytes.
+    assert_eq!(
+        parse_readings::<u128>("\u{a0}\u{a0}4ö"),
+        Err(error(1, 4, ParseErrorKind::InvalidDigit))
+    );
+    assert_eq!(
+        parse_readings::<u128>("  -5"),
+        Err(error(1, 3, ParseErrorKind::InvalidDigit))
+    );
+    assert_eq!(
+        parse_readings::<u8>("255\n 256"),
+        Err(error(2, 2, ParseErrorKind::Overflow))
+    );
+    assert_eq!(
+        parse_readings::<i8>("-129"),
+        Err(error(1, 1, ParseErrorKind::Overflow))
+    );
+    for empty in ["-", "0x", "__", "+0x_"] {
+        assert_eq!(
+            parse_readings::<i32>(empty),
+            Err(error(1, 1, ParseErrorKind::NoDigits))
+        );
+    }
+    assert_eq!(
+        parse_readings::<u128>("1\nthree\n")
+            .unwrap_err()
+            .to_string(),
+        "Line 2, column 1: Invalid digit in reading."
+    );
+}
+
+#[test]
+fn lenient_skips_and_counts_bad_lines() {
+    use namt_preventative_collapse::{parse_readings_lenient, ParseError, ParseErrorKind};
+    let parsed = parse_readings_lenient::<u8>("# Log\n1\nx\n\n2\n300\n3\n");
+    assert_eq!(parsed.readings, vec![1, 2, 3]);
+    assert_eq!(
+        parsed.skipped,
+        vec![
+            ParseError {
+                line: 3,
+                column: 1,
+                kind: ParseErrorKind::InvalidDigit
+            },
+            ParseError {
+                line: 6,
+                column: 1,
+                kind: ParseErrorKind::Overflow
+            }
+        ]
+    );
+    assert!(parse_readings_lenient::<u8>("1\n2\n").skipped.is_empty());
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use common::pseudo_random_signed_series;
+
+const STRATEGIES: [namt_preventative_collapse::Strategy; 3] = [
+    namt_preventative_collapse::Strategy::Resort,
+    namt_preventative_collapse::Strategy::Hashed,
+    namt_preventative_collapse::Strategy::Incremental,
+];
+
+const RULES: [namt_preventative_collapse::PairRule; 3] = [
+    namt_preventative_collapse::PairRule::Legacy,
+    namt_preventative_collapse::PairRule::Strict {
+        distinct_values: false,
+    },
+    namt_preventative_collapse::PairRule::Strict {
+        distinct_values: true,
+    },
+];
+
+///Brute force oracle in `i128`, which no reading type in these tests can overflow.
+/// Indices whose reading no pair of the preceding `period` readings reaches under `rule`.
+fn oracle(
+    vector: &[i128],
+    period: usize,
+    rule: namt_preventative_collapse::PairRule,
+) -> Vec<usize> {
+    use namt_preventative_collapse::PairRule;
+    (period..vector.len())
+        .filter(|c| {
+            let (window, target) = (&vector[c - period..*c], vector[*c]);
+            let min = *window.iter().min().unwrap();
+            let max = *window.iter().max().unwrap();
+            //The early warnings come first under every rule.
+            let pair = |i: usize, j: usize| match rule {
+                PairRule::Legacy => {
+                    window[i] + window[j] == target || window[i] - window[j] == target
+                }
+                PairRule::Strict { distinct_values } => {
+                    i != j
+                        && window[i] + window[j] == target
+                        && (!distinct_values || window[i] != window[j])
+                }
+            };
+            2 * max < target
+                || 2 * min > target
+                || !(0..period).any(|i| (0..period).any(|j| pair(i, j)))
+        })
+        .collect()
+}
+
+fn indices<T>(anomalies: Vec<namt_preventative_collapse::CollapseError<T>>) -> Vec<usize>
+where
+    T: namt_preventative_collapse::Reading,
+{
+    anomalies.iter().map(|error| error.index()).collect()
+}
+
+///Every strategy and rule, on `series` narrowed to `T`, against the oracle.
+fn assert_matches_oracle<T>(series: &[i128], period: usize)
+where
+    T: namt_preventative_collapse::Reading + TryFrom<i128>,
+    T::Error: std::fmt::Debug,
+{
+    use namt_preventative_collapse::PairRule;
+    let readings: Vec<T> = series.iter().map(|x| T::try_from(*x).unwrap()).collect();
+    let expected = oracle(series, period, PairRule::Legacy);
+    for strategy in STRATEGIES {
+        assert_eq!(
+            indices(strategy.collapse_scan(&readings, period)),
+            expected,
+            "{:?} {:?} period {}",
+            strategy,
+            series,
+            period
+        );
+    }
+    for rule in RULES {
+        assert_eq!(
+            indices(rule.collapse_scan(&readings, period)),
+            oracle(series, period, rule),
+            "{:?} {:?} period {}",
+            rule,
+            series,
+            period
+        );
+    }
+}
+
+#[test]
+fn narrow_unsigned_readings_match_oracle() {
+    for seed in 0..50 {
+        let series = pseudo_random_signed_series(seed, 40, 16, false);
+        for period in 1..5 {
+            assert_matches_oracle::<u8>(&series, period);
+            assert_matches_oracle::<u16>(&series, period);
+            assert_matches_oracle::<u32>(&series, period);
+            assert_matches_oracle::<u64>(&series, period);
+            assert_matches_oracle::<usize>(&series, period);
+        }
+    }
+}
+
+#[test]
+fn signed_readings_match_oracle() {
+    for seed in 0..50 {
+        let series = pseudo_random_signed_series(seed, 40, 16, true);
+        for period in 1..5 {
+            assert_matches_oracle::<i8>(&series, period);
+            assert_matches_oracle::<i32>(&series, period);
+            assert_matches_oracle::<i64>(&series, period);
+            assert_matches_oracle::<i128>(&series, period);
+        }
+    }
+}
+
+#[test]
+fn full_range_of_narrow_types_never_overflows() {
+    for seed in 0..50 {
+        let series = pseudo_random_signed_series(seed, 40, 256, false);
+        let signed = pseudo_random_signed_series(seed, 40, 256, true);
+        for period in 1..5 {
+            assert_matches_oracle::<u8>(&series, period);
+            assert_matches_oracle::<i8>(&signed, period);
+        }
+    }
+    assert_matches_oracle::<i8>(&[-128, 127, -1, 127, -128, 0, -128, 126, -2], 3);
+    assert_matches_oracle::<u8>(&[255, 128, 127, 255, 0, 254, 128, 1, 255], 3);
+}
+
+#[test]
+fn narrow_readings_agree_with_u128_on_test_inputs() {
+    use namt_preventative_collapse::{collapse_scan, parse_readings};
+    use std::fs;
+    let numbers = fs::read_to_string("../test_inputs/mini_mine.txt").unwrap();
+    let wide = parse_readings::<u128>(&numbers).unwrap();
+    let narrow = parse_readings::<u64>(&numbers).unwrap();
+    assert_eq!(
+        indices(collapse_scan(&narrow, 5)),
+        indices(collapse_scan(&wide, 5))
+    );
+}
+
+#[test]
+fn negative_readings_reach_negative_targets() {
+    use namt_preventative_collapse::{collapse_check, CollapseError};
+    //-7 = -3 + -4
+    let vec: Vec<i32> = vec![-3, 10, -4, -7];
+    assert_eq!(collapse_check(&vec, 3), Ok(()));
+    //-9 is below twice the minimum, -8.
+    let vec: Vec<i32> = vec![-3, 10, -4, -9];
+    assert_eq!(
+        collapse_check(&vec, 3),
+        Err(CollapseError::MinTooBig {
+            index: 3,
+            target: -9,
+            min: -4,
+            max: 10
+        })
+    );
+}
+
+#[test]
+fn monitor_accepts_signed_readings() {
+    use namt_preventative_collapse::{CollapseMonitor, ResortWindow, Verdict};
+    let mut monitor = CollapseMonitor::<ResortWindow<i16>>::with_strategy(2);
+    assert_eq!(monitor.push(-5), Ok(Verdict::Warming));
+    assert_eq!(monitor.push(2), Ok(Verdict::Warming));
+    assert_eq!(monitor.push(-3), Ok(Verdict::Safe));
+    assert_eq!(monitor.push(-1), Ok(Verdict::Safe));
+    assert_eq!(monitor.push(5).unwrap_err().target(), 5);
+}
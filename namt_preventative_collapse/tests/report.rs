@@ -0,0 +1,124 @@
+mod common;
+
+use common::read;
+
+#[test]
+fn report_matches_scan_and_check() {
+    use namt_preventative_collapse::{collapse_check, collapse_scan, Report, Strategy};
+    for (path, period) in [
+        ("../test_inputs/mini_mine.txt", 5),
+        ("../test_inputs/challenge_input.txt", 100),
+    ] {
+        let numbers = read(path);
+        for strategy in [Strategy::Resort, Strategy::Hashed, Strategy::Incremental] {
+            let report = Report::new(&numbers, period, strategy, true);
+            assert_eq!(report.anomalies, collapse_scan(&numbers, period));
+            assert_eq!(report.summary.strategy, Some(strategy));
+            let report = Report::new(&numbers, period, strategy, false);
+            assert_eq!(
+                report.anomalies,
+                collapse_check(&numbers, period)
+                    .err()
+                    .into_iter()
+                    .collect::<Vec<_>>()
+            );
+            assert!(!report.summary.safe);
+            assert_eq!(report.summary.readings, numbers.len());
+            assert_eq!(report.summary.period, Some(period));
+            assert_eq!(report.summary.min_reading, numbers.iter().min().copied());
+            assert_eq!(report.summary.max_reading, numbers.iter().max().copied());
+        }
+    }
+}
+
+#[test]
+fn witnesses_only_when_safe() {
+    use namt_preventative_collapse::{collapse_witnesses, Report, Strategy};
+    let numbers = read("../test_inputs/mini_mine.txt");
+    let report = Report::new(&numbers, 5, Strategy::Resort, true).with_witnesses(&numbers);
+    assert!(report.witnesses.is_empty());
+    let report =
+        Report::new(&numbers[..14], 5, Strategy::Resort, true).with_witnesses(&numbers[..14]);
+    assert!(report.summary.safe);
+    assert_eq!(
+        report.witnesses,
+        collapse_witnesses(&numbers[..14], 5).unwrap()
+    );
+    assert_eq!(report.witnesses.len(), 9);
+
+    let empty: Report = Report::new(&[], 5, Strategy::Resort, false);
+    assert_eq!(empty.summary.min_reading, None);
+    assert!(empty.summary.safe);
+}
+
+#[test]
+fn records_report_sensors() {
+    use namt_preventative_collapse::{parse_csv, CsvFormat, Report, Strategy};
+    use std::fs;
+    use std::time::Duration;
+    let text = fs::read_to_string("../test_inputs/mine_log.csv").unwrap();
+    let records = parse_csv::<u128>(&text, &CsvFormat::default()).unwrap();
+    let report = Report::records(&records, 5, Strategy::Resort, true);
+    assert_eq!(report.summary.sensors, Some(2));
+    assert_eq!(report.summary.readings, 40);
+    assert_eq!(report.anomalies.len(), 1);
+    assert_eq!(report.anomalies[0].record, 28);
+
+    let timed = Report::records_timed(&records, Duration::from_secs(300), true);
+    assert_eq!(timed.anomalies, report.anomalies);
+    assert_eq!(timed.summary.period, None);
+    assert_eq!(timed.summary.strategy, None);
+    assert_eq!(timed.summary.window_seconds, Some(300.0));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes_to_flat_json() {
+    use namt_preventative_collapse::{
+        parse_csv, CollapseError, CsvFormat, Report, Severity, Strategy, Verdict,
+    };
+    let numbers = read("../test_inputs/mini_mine.txt");
+    let report = Report::new(&numbers, 5, Strategy::Incremental, false);
+    assert_eq!(
+        serde_json::to_string(&report).unwrap(),
+        "{\"readings\":20,\"min_reading\":15,\"max_reading\":576,\"period\":5,\"strategy\":\"incremental\",\
+         \"safe\":false,\"anomalies\":[{\"kind\":\"MinTooBig\",\"index\":14,\"target\":127,\"min\":95,\"max\":182}]}"
+    );
+
+    let records = parse_csv::<u128>("06:00,B7,1\n06:01,B7,3\n", &CsvFormat::default()).unwrap();
+    let report = Report::records(&records, 1, Strategy::Resort, true);
+    assert_eq!(
+        serde_json::to_value(&report.anomalies[0]).unwrap(),
+        serde_json::json!({
+            "record": 1,
+            "timestamp": "06:01",
+            "sensor": "B7",
+            "kind": "MaxTooSmall",
+            "index": 1,
+            "target": 3,
+            "min": 1,
+            "max": 1
+        })
+    );
+
+    //Readings past u64 stay exact.
+    let error = CollapseError::EmptyWindow {
+        index: 0,
+        target: u128::MAX,
+    };
+    assert_eq!(
+        serde_json::to_string(&error).unwrap(),
+        format!(
+            "{{\"kind\":\"EmptyWindow\",\"index\":0,\"target\":{}}}",
+            u128::MAX
+        )
+    );
+    assert_eq!(
+        serde_json::to_string(&Verdict::Warming).unwrap(),
+        "\"Warming\""
+    );
+    assert_eq!(
+        serde_json::to_string(&Severity::Critical).unwrap(),
+        "\"Critical\""
+    );
+}
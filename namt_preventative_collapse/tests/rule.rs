@@ -0,0 +1,138 @@
+mod common;
+
+use common::pseudo_random_series;
+
+///Brute force oracle. Indices whose reading no pair of the preceding `period` readings reaches.
+fn oracle(vector: &[u128], period: usize, accepts: impl Fn(&[u128], u128) -> bool) -> Vec<usize> {
+    (period..vector.len())
+        .filter(|c| !accepts(&vector[c - period..*c], vector[*c]))
+        .collect()
+}
+
+///The early warnings come first, so a difference never rescues a reading below twice the minimum.
+fn legacy(window: &[u128], target: u128) -> bool {
+    let min = *window.iter().min().unwrap();
+    let max = *window.iter().max().unwrap();
+    2 * max >= target
+        && 2 * min <= target
+        && window.iter().any(|a| window.contains(&target.abs_diff(*a)))
+}
+
+fn strict(window: &[u128], target: u128, distinct_values: bool) -> bool {
+    (0..window.len()).any(|i| {
+        (i + 1..window.len()).any(|j| {
+            window[i] + window[j] == target && (!distinct_values || window[i] != window[j])
+        })
+    })
+}
+
+fn indices(anomalies: Vec<namt_preventative_collapse::CollapseError>) -> Vec<usize> {
+    anomalies.iter().map(|error| error.index()).collect()
+}
+
+#[test]
+fn legacy_rule_matches_oracle() {
+    use namt_preventative_collapse::PairRule;
+    for seed in 0..100 {
+        let numbers = pseudo_random_series(seed, 60, 16);
+        for period in 1..6 {
+            assert_eq!(
+                indices(PairRule::Legacy.collapse_scan(&numbers, period)),
+                oracle(&numbers, period, legacy),
+                "seed {} period {}",
+                seed,
+                period
+            );
+        }
+    }
+}
+
+#[test]
+fn strict_rule_matches_oracle() {
+    use namt_preventative_collapse::PairRule;
+    for seed in 0..100 {
+        let numbers = pseudo_random_series(seed, 60, 16);
+        for period in 1..6 {
+            for distinct_values in [false, true] {
+                assert_eq!(
+                    indices(PairRule::Strict { distinct_values }.collapse_scan(&numbers, period)),
+                    oracle(&numbers, period, |window, target| strict(
+                        window,
+                        target,
+                        distinct_values
+                    )),
+                    "seed {} period {} distinct_values {}",
+                    seed,
+                    period,
+                    distinct_values
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn strict_rule_matches_oracle_on_mega_mine() {
+    use namt_preventative_collapse::parse_readings;
+    use namt_preventative_collapse::PairRule;
+    use std::fs;
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
+    let numbers = &numbers[..2000];
+    let rule = PairRule::Strict {
+        distinct_values: false,
+    };
+    assert_eq!(
+        indices(rule.collapse_scan(numbers, 100)),
+        oracle(numbers, 100, |window, target| strict(window, target, false))
+    );
+}
+
+#[test]
+fn strict_rule_rejects_one_slot_used_twice() {
+    use namt_preventative_collapse::{CollapseError, PairRule};
+    let vec = vec![3, 1, 4, 8];
+    assert_eq!(PairRule::Legacy.collapse_check(&vec, 3), Ok(()));
+    assert_eq!(
+        PairRule::Strict {
+            distinct_values: false
+        }
+        .collapse_check(&vec, 3),
+        Err(CollapseError::NoPairFound {
+            index: 3,
+            target: 8,
+            min: 1,
+            max: 4
+        })
+    );
+}
+
+#[test]
+fn strict_rule_rejects_differences() {
+    use namt_preventative_collapse::PairRule;
+    let vec = vec![9, 2, 6, 7];
+    assert_eq!(PairRule::Legacy.collapse_check(&vec, 3), Ok(()));
+    assert!(PairRule::Strict {
+        distinct_values: false
+    }
+    .collapse_check(&vec, 3)
+    .is_err());
+}
+
+#[test]
+fn strict_rule_distinct_values_is_configurable() {
+    use namt_preventative_collapse::PairRule;
+    let vec = vec![3, 1, 3, 6];
+    assert_eq!(
+        PairRule::Strict {
+            distinct_values: false
+        }
+        .collapse_check(&vec, 3),
+        Ok(())
+    );
+    assert!(PairRule::Strict {
+        distinct_values: true
+    }
+    .collapse_check(&vec, 3)
+    .is_err());
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::read;
+
+#[test]
+fn iter_matches_slices() {
+    use namt_preventative_collapse::{
+        collapse_check, collapse_check_iter, collapse_scan, collapse_scan_iter,
+    };
+    for (path, period) in [
+        ("../test_inputs/mini_mine.txt", 5),
+        ("../test_inputs/challenge_input.txt", 100),
+    ] {
+        let numbers = read(path);
+        assert_eq!(
+            collapse_check_iter(numbers.iter().copied(), period),
+            collapse_check(&numbers, period)
+        );
+        assert_eq!(
+            collapse_scan_iter(numbers.iter().copied(), period).collect::<Vec<_>>(),
+            collapse_scan(&numbers, period)
+        );
+        //Safe up to the first anomaly.
+        let first = collapse_check(&numbers, period).unwrap_err().index();
+        assert_eq!(
+            collapse_check_iter(numbers[..first].iter().copied(), period),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+fn checks_a_ring_buffer() {
+    use namt_preventative_collapse::{collapse_check, collapse_check_iter};
+    use std::collections::VecDeque;
+    let numbers = read("../test_inputs/mini_mine.txt");
+    //Wrapped around, so its readings are not one contiguous slice.
+    let mut buffer: VecDeque<u128> = VecDeque::with_capacity(numbers.len());
+    for &reading in numbers.iter().rev() {
+        buffer.push_front(reading);
+    }
+    assert_eq!(
+        collapse_check_iter(buffer.iter().copied(), 5),
+        collapse_check(&numbers, 5)
+    );
+    //Fewer readings than the period are safe, as for slices.
+    assert_eq!(collapse_check_iter(Vec::<u8>::new(), 5), Ok(()));
+}
+
+#[test]
+fn parses_lazily() {
+    use namt_preventative_collapse::{parse_readings, parse_readings_iter, ParseErrorKind};
+    let text = std::fs::read_to_string("../test_inputs/challenge_input.txt").unwrap();
+    assert_eq!(
+        parse_readings_iter::<u128>(&text).collect::<Result<Vec<_>, _>>(),
+        parse_readings(&text)
+    );
+    let readings: Vec<_> = parse_readings_iter::<u8>("# header\n1\n\nx\n300\n2\n").collect();
+    assert_eq!(readings.len(), 4);
+    assert_eq!(readings[0], Ok(1));
+    assert_eq!(readings[1].as_ref().unwrap_err().line, 4);
+    assert_eq!(
+        readings[2].as_ref().unwrap_err().kind,
+        ParseErrorKind::Overflow
+    );
+    assert_eq!(readings[3], Ok(2));
+}
+
+#[test]
+#[should_panic]
+fn zero_period_panics() {
+    use namt_preventative_collapse::collapse_check_iter;
+    let _ = collapse_check_iter([1, 2, 3], 0);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn checks_a_mapped_file() {
+    use namt_preventative_collapse::{collapse_check, MappedReadings};
+    use std::fs;
+    let mapped = MappedReadings::open("../test_inputs/challenge_input.txt").unwrap();
+    let numbers = read("../test_inputs/challenge_input.txt");
+    assert_eq!(
+        mapped.collapse_check::<u128>(100),
+        Ok(collapse_check(&numbers, 100))
+    );
+    assert_eq!(
+        mapped.readings::<u128>().collect::<Result<Vec<_>, _>>(),
+        Ok(numbers)
+    );
+
+    let directory = std::env::temp_dir();
+    let path = directory.join(format!("namt_source_{}.txt", std::process::id()));
+    fs::write(&path, "1\n2\n3\nfour\n").unwrap();
+    let mapped = MappedReadings::open(&path).unwrap();
+    assert_eq!(mapped.collapse_check::<u32>(3).unwrap_err().line, 4);
+    fs::write(&path, "").unwrap();
+    let mapped = MappedReadings::open(&path).unwrap();
+    assert_eq!(mapped.text(), "");
+    assert_eq!(mapped.collapse_check::<u32>(3), Ok(Ok(())));
+    fs::write(&path, [0xff, b'\n']).unwrap();
+    let error = MappedReadings::open(&path).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    fs::remove_file(&path).unwrap();
+}
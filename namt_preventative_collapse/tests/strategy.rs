@@ -0,0 +1,139 @@
+mod common;
+
+use common::{pseudo_random_series, read};
+
+const STRATEGIES: [namt_preventative_collapse::Strategy; 3] = [
+    namt_preventative_collapse::Strategy::Resort,
+    namt_preventative_collapse::Strategy::Hashed,
+    namt_preventative_collapse::Strategy::Incremental,
+];
+
+#[test]
+fn strategies_agree_on_test_inputs() {
+    use namt_preventative_collapse::collapse_check;
+    let numbers = read("../test_inputs/mini_mine.txt");
+    let expected = collapse_check(&numbers, 5);
+    for strategy in STRATEGIES {
+        assert_eq!(strategy.collapse_check(&numbers, 5), expected);
+    }
+
+    let numbers = read("../test_inputs/mega_mine.txt");
+    for period in [25, 100, 1000] {
+        let expected = collapse_check(&numbers, period);
+        for strategy in STRATEGIES {
+            assert_eq!(strategy.collapse_check(&numbers, period), expected);
+        }
+    }
+}
+
+#[test]
+fn strategies_agree_on_pseudo_random_series() {
+    use namt_preventative_collapse::collapse_scan;
+    for seed in 0..200 {
+        let numbers = pseudo_random_series(seed, 40, 12);
+        for period in 1..6 {
+            //Check every prefix, so every index gets compared, not only the first anomaly.
+            for end in period + 1..numbers.len() {
+                let expected = collapse_scan(&numbers[..end], period)
+                    .into_iter()
+                    .next()
+                    .map_or(Ok(()), Err);
+                for strategy in STRATEGIES {
+                    assert_eq!(
+                        strategy.collapse_check(&numbers[..end], period),
+                        expected,
+                        "{:?} seed {} period {} end {}",
+                        strategy,
+                        seed,
+                        period,
+                        end
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn experiments_are_strategies() {
+    use namt_preventative_collapse::bench_iterations::{
+        AllocAndSortOnEachLoop, MoreShortCircuiting, NarrowingSearch, NoShortCircuit,
+    };
+    use namt_preventative_collapse::{collapse_check, collapse_check_with};
+    let numbers = read("../test_inputs/mega_mine.txt");
+    let expected = collapse_check(&numbers, 100);
+    //Alloc and sort checks against every earlier reading, so its minimum is the smallest reading so far.
+    assert_eq!(
+        collapse_check_with::<AllocAndSortOnEachLoop>(&numbers, 100).map_err(|error| error.index()),
+        expected.clone().map_err(|error| error.index())
+    );
+    assert_eq!(
+        collapse_check_with::<MoreShortCircuiting>(&numbers, 100),
+        expected
+    );
+    //Narrowing reports the narrowed minimum as too big, where the others find no pair.
+    assert_eq!(
+        collapse_check_with::<NarrowingSearch>(&numbers, 100).map_err(|error| error.index()),
+        expected.clone().map_err(|error| error.index())
+    );
+    assert_eq!(
+        collapse_check_with::<NoShortCircuit>(&numbers, 100),
+        expected
+    );
+}
+
+#[test]
+fn alloc_and_sort_checks_against_every_earlier_reading() {
+    use namt_preventative_collapse::bench_iterations::collapse_check_alloc_and_sort_on_each_loop;
+    use namt_preventative_collapse::{collapse_check, CollapseError};
+    //6 is out of reach of 10, 16 and 26, but not of 2 and 4, which left the window.
+    let numbers = vec![2, 4, 6, 10, 16, 26, 6];
+    assert_eq!(
+        collapse_check(&numbers, 3),
+        Err(CollapseError::MinTooBig {
+            index: 6,
+            target: 6,
+            min: 10,
+            max: 26
+        })
+    );
+    assert_eq!(
+        collapse_check_alloc_and_sort_on_each_loop(&numbers, 3),
+        Ok(())
+    );
+}
+
+#[test]
+fn scan_with_every_production_strategy() {
+    use namt_preventative_collapse::collapse_scan;
+    for seed in 0..50 {
+        let numbers = pseudo_random_series(seed, 200, 30);
+        let expected = collapse_scan(&numbers, 4);
+        for strategy in STRATEGIES {
+            assert_eq!(strategy.collapse_scan(&numbers, 4), expected);
+        }
+    }
+}
+
+#[test]
+fn monitor_with_every_production_strategy() {
+    use namt_preventative_collapse::{
+        collapse_scan, CollapseMonitor, HashedWindow, IncrementalWindow,
+    };
+    let numbers = read("../test_inputs/mega_mine.txt");
+    let expected = collapse_scan(&numbers, 100);
+
+    let mut monitor = CollapseMonitor::<HashedWindow>::with_strategy(100);
+    let anomalies: Vec<_> = numbers
+        .iter()
+        .filter_map(|reading| monitor.push(*reading).err())
+        .collect();
+    assert_eq!(anomalies, expected);
+
+    let mut monitor = CollapseMonitor::<IncrementalWindow>::with_strategy(100);
+    let anomalies: Vec<_> = numbers
+        .iter()
+        .filter_map(|reading| monitor.push(*reading).err())
+        .collect();
+    assert_eq!(anomalies, expected);
+}
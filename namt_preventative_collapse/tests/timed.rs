@@ -0,0 +1,276 @@
+mod common;
+
+use common::{pseudo_random_series, read};
+use std::time::Duration;
+
+fn uniform(numbers: &[u128], start: u64, step: u64) -> Vec<(Duration, u128)> {
+    numbers
+        .iter()
+        .enumerate()
+        .map(|(c, &x)| (Duration::from_secs(start + c as u64 * step), x))
+        .collect()
+}
+
+#[test]
+fn uniform_sampling_matches_period_on_test_inputs() {
+    use namt_preventative_collapse::{
+        collapse_check, collapse_check_timed, collapse_scan, collapse_scan_timed,
+    };
+    for (path, period) in [
+        ("../test_inputs/mini_mine.txt", 5),
+        ("../test_inputs/mega_mine.txt", 100),
+        ("../test_inputs/challenge_input.txt", 100),
+    ] {
+        let numbers = read(path);
+        for (start, step) in [(0, 1), (1709272800, 7)] {
+            let timed = uniform(&numbers, start, step);
+            let window = Duration::from_secs(period as u64 * step);
+            assert_eq!(
+                collapse_check_timed(&timed, window),
+                collapse_check(&numbers, period)
+            );
+            assert_eq!(
+                collapse_scan_timed(&timed, window),
+                collapse_scan(&numbers, period)
+            );
+        }
+    }
+}
+
+///The readings logged within `window` before each reading, checked with `collapse_check`.
+fn oracle(
+    timed: &[(Duration, u128)],
+    window: Duration,
+) -> Vec<namt_preventative_collapse::CollapseError> {
+    use namt_preventative_collapse::{collapse_check, CollapseError};
+    let first = timed[0].0;
+    let mut anomalies = vec![];
+    for (c, &(time, target)) in timed.iter().enumerate() {
+        if time < first + window {
+            continue; //Warming.
+        }
+        let mut vector: Vec<u128> = timed[..c]
+            .iter()
+            .filter(|(logged, _)| *logged >= time - window)
+            .map(|&(_, x)| x)
+            .collect();
+        if vector.is_empty() {
+            anomalies.push(CollapseError::EmptyWindow { index: c, target });
+            continue;
+        }
+        let period = vector.len();
+        vector.push(target);
+        if let Err(error) = collapse_check(&vector, period) {
+            let (min, max) = error.window().unwrap();
+            anomalies.push(match error {
+                CollapseError::MaxTooSmall { .. } => CollapseError::MaxTooSmall {
+                    index: c,
+                    target,
+                    min,
+                    max,
+                },
+                CollapseError::MinTooBig { .. } => CollapseError::MinTooBig {
+                    index: c,
+                    target,
+                    min,
+                    max,
+                },
+                _ => CollapseError::NoPairFound {
+                    index: c,
+                    target,
+                    min,
+                    max,
+                },
+            });
+        }
+    }
+    anomalies
+}
+
+#[test]
+fn irregular_sampling_matches_oracle() {
+    use namt_preventative_collapse::collapse_scan_timed;
+    for seed in 0..60 {
+        let numbers = pseudo_random_series(seed, 80, 20);
+        //Gaps from 0 to 5 seconds, so bursts share a timestamp and some windows empty out.
+        let gaps = pseudo_random_series(seed + 1000, 80, 6);
+        let mut time = 0;
+        let timed: Vec<(Duration, u128)> = numbers
+            .iter()
+            .zip(gaps)
+            .map(|(&x, gap)| {
+                time += gap as u64;
+                (Duration::from_secs(time), x)
+            })
+            .collect();
+        for window in [1, 3, 8] {
+            let window = Duration::from_secs(window);
+            assert_eq!(collapse_scan_timed(&timed, window), oracle(&timed, window));
+        }
+    }
+}
+
+#[test]
+fn window_expires_by_time() {
+    use namt_preventative_collapse::{CollapseError, TimedMonitor, Verdict};
+    let seconds = Duration::from_secs;
+    let mut monitor = TimedMonitor::new(seconds(10));
+    assert!(monitor.is_empty());
+    //A burst at the start, then one reading every 10 seconds.
+    for reading in [1, 2, 3, 4] {
+        assert_eq!(monitor.push(seconds(0), reading), Ok(Verdict::Warming));
+    }
+    assert_eq!(monitor.push(seconds(9), 5), Ok(Verdict::Warming));
+    //The burst is exactly 10 seconds back, so still in the window.
+    assert_eq!(monitor.push(seconds(10), 9), Ok(Verdict::Safe));
+    //Only 9 and 5 are left.
+    assert_eq!(
+        monitor.push(seconds(11), 13),
+        Err(CollapseError::NoPairFound {
+            index: 6,
+            target: 13,
+            min: 5,
+            max: 9
+        })
+    );
+    //Only 13 is left.
+    assert_eq!(monitor.push(seconds(21), 26), Ok(Verdict::Safe));
+    assert_eq!(
+        monitor.push(seconds(40), 6),
+        Err(CollapseError::EmptyWindow {
+            index: 8,
+            target: 6
+        })
+    );
+    //The reading with an empty window still entered it.
+    assert_eq!(monitor.push(seconds(41), 12), Ok(Verdict::Safe));
+    assert_eq!(monitor.len(), 10);
+    assert_eq!(monitor.window(), seconds(10));
+}
+
+#[test]
+fn out_of_order_readings_are_rejected() {
+    use namt_preventative_collapse::{CollapseError, TimedMonitor, Verdict};
+    let seconds = Duration::from_secs;
+    let mut monitor = TimedMonitor::new(seconds(2));
+    monitor.push(seconds(5), 1).unwrap();
+    monitor.push(seconds(6), 1).unwrap();
+    assert_eq!(
+        monitor.push(seconds(4), 100),
+        Err(CollapseError::BadTimestamp {
+            index: 2,
+            target: 100
+        })
+    );
+    //100 never entered the window.
+    assert_eq!(monitor.push(seconds(7), 2), Ok(Verdict::Safe));
+    assert_eq!(
+        monitor.push(seconds(8), 101),
+        Err(CollapseError::MaxTooSmall {
+            index: 4,
+            target: 101,
+            min: 1,
+            max: 2
+        })
+    );
+}
+
+#[test]
+#[should_panic]
+fn zero_window_panics() {
+    use namt_preventative_collapse::TimedMonitor;
+    TimedMonitor::<u128>::new(Duration::ZERO);
+}
+
+#[test]
+fn parses_timestamps() {
+    use namt_preventative_collapse::parse_timestamp;
+    let seconds = |s| Some(Duration::from_secs(s));
+    assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), seconds(0));
+    assert_eq!(parse_timestamp("0"), seconds(0));
+    assert_eq!(parse_timestamp(" 1709272800 "), seconds(1709272800));
+    assert_eq!(parse_timestamp("2024-03-01T06:00:00Z"), seconds(1709272800));
+    assert_eq!(parse_timestamp("2024-03-01t06:00:00z"), seconds(1709272800));
+    assert_eq!(parse_timestamp("2024-03-01 06:00:00"), seconds(1709272800));
+    assert_eq!(
+        parse_timestamp("2024-03-01T01:30:00-04:30"),
+        seconds(1709272800)
+    );
+    assert_eq!(
+        parse_timestamp("2024-02-29T23:59:59.123456789Z"),
+        Some(Duration::new(1709251199, 123456789))
+    );
+    assert_eq!(parse_timestamp("2000-02-29T00:00:00Z"), seconds(951782400));
+    assert_eq!(parse_timestamp("2100-03-01T00:00:00Z"), seconds(4107542400));
+    for bad in [
+        "",
+        ".",
+        "06:00",
+        "2024-03-01",
+        "2023-02-29T00:00:00Z",
+        "2100-02-29T00:00:00Z",
+        "2024-13-01T00:00:00Z",
+        "2024-03-01T24:00:00Z",
+        "2024-03-01T06:60:00Z",
+        "2024-03-01T06:00:60Z",
+        "2024-3-1T06:00:00Z",
+        "2024-03-01T06:00:00+2",
+        "2024-03-01T06:00:00Zulu",
+        "1969-12-31T23:59:59Z",
+        "1970-01-01T00:00:00+00:01",
+        "-5",
+    ] {
+        assert_eq!(parse_timestamp(bad), None, "{:?}", bad);
+    }
+}
+
+#[test]
+fn records_timed_match_period_when_uniform() {
+    use namt_preventative_collapse::{
+        collapse_check_records, collapse_check_records_timed, collapse_scan_records,
+        collapse_scan_records_timed, parse_csv, CsvFormat, Strategy,
+    };
+    use std::fs;
+    let text = fs::read_to_string("../test_inputs/mine_log.csv").unwrap();
+    let records = parse_csv::<u128>(&text, &CsvFormat::default()).unwrap();
+    for period in 1..8 {
+        let window = Duration::from_secs(period as u64 * 60);
+        assert_eq!(
+            collapse_scan_records_timed(&records, window),
+            collapse_scan_records(&records, period, Strategy::Resort)
+        );
+        assert_eq!(
+            collapse_check_records_timed(&records, window),
+            collapse_check_records(&records, period, Strategy::Resort)
+        );
+    }
+}
+
+#[test]
+fn records_with_bad_timestamps() {
+    use namt_preventative_collapse::{
+        collapse_scan_records_timed, parse_csv, CollapseError, CsvFormat,
+    };
+    let text = "10,B7,1\n11,B7,1\nsoon,B7,50\n12,B7,2\n9,B7,60\n13,B7,3\n";
+    let records = parse_csv::<u128>(text, &CsvFormat::default()).unwrap();
+    let violations = collapse_scan_records_timed(&records, Duration::from_secs(2));
+    let errors: Vec<_> = violations
+        .iter()
+        .map(|violation| violation.error.clone())
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            CollapseError::BadTimestamp {
+                index: 2,
+                target: 50
+            },
+            CollapseError::BadTimestamp {
+                index: 4,
+                target: 60
+            },
+        ]
+    );
+    assert_eq!(violations[0].timestamp, "soon");
+    assert_eq!(violations[1].record, 4);
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use common::{pseudo_random_series, read};
+
+///The `u128` error, with its readings as `f64`.
+fn widen(
+    error: namt_preventative_collapse::CollapseError,
+) -> namt_preventative_collapse::CollapseError<f64> {
+    use namt_preventative_collapse::CollapseError;
+    match error {
+        CollapseError::MaxTooSmall {
+            index,
+            target,
+            min,
+            max,
+        } => CollapseError::MaxTooSmall {
+            index,
+            target: target as f64,
+            min: min as f64,
+            max: max as f64,
+        },
+        CollapseError::MinTooBig {
+            index,
+            target,
+            min,
+            max,
+        } => CollapseError::MinTooBig {
+            index,
+            target: target as f64,
+            min: min as f64,
+            max: max as f64,
+        },
+        CollapseError::NoPairFound {
+            index,
+            target,
+            min,
+            max,
+        } => CollapseError::NoPairFound {
+            index,
+            target: target as f64,
+            min: min as f64,
+            max: max as f64,
+        },
+        other => panic!("collapse_check never reports {:?}", other),
+    }
+}
+
+#[test]
+fn zero_tolerance_matches_collapse_check() {
+    use namt_preventative_collapse::{collapse_check, collapse_check_tolerant, Tolerance};
+    let numbers = read("../test_inputs/mini_mine.txt");
+    let loads: Vec<f64> = numbers.iter().map(|x| *x as f64).collect();
+    assert_eq!(
+        collapse_check_tolerant(&loads, 5, Tolerance::Absolute(0.0)),
+        collapse_check(&numbers, 5).map_err(widen)
+    );
+
+    for seed in 0..100 {
+        let numbers = pseudo_random_series(seed, 60, 16);
+        let loads: Vec<f64> = numbers.iter().map(|x| *x as f64).collect();
+        for period in 1..6 {
+            assert_eq!(
+                collapse_check_tolerant(&loads, period, Tolerance::Absolute(0.0)),
+                collapse_check(&numbers, period).map_err(widen),
+                "seed {} period {}",
+                seed,
+                period
+            );
+        }
+    }
+}
+
+#[test]
+fn noisy_readings_pass_within_tolerance() {
+    use namt_preventative_collapse::{collapse_check, collapse_check_tolerant, Tolerance};
+    let numbers = read("../test_inputs/mini_mine.txt");
+    //Up to 0.004 kN of noise on every reading.
+    let loads: Vec<f64> = numbers
+        .iter()
+        .enumerate()
+        .map(|(c, x)| *x as f64 + [0.004, -0.003, 0.001, -0.004][c % 4])
+        .collect();
+    let expected = collapse_check(&numbers, 5).unwrap_err().index();
+    let result = collapse_check_tolerant(&loads, 5, Tolerance::Absolute(0.02));
+    assert_eq!(result.unwrap_err().index(), expected);
+    let result = collapse_check_tolerant(&loads[..expected], 5, Tolerance::Absolute(0.02));
+    assert_eq!(result, Ok(()));
+    let result = collapse_check_tolerant(&loads[..expected], 5, Tolerance::Absolute(0.0));
+    assert!(result.is_err());
+}
+
+#[test]
+fn relative_tolerance_scales_with_the_reading() {
+    use namt_preventative_collapse::{pair_exists_tolerant, CollapseError, Tolerance};
+    let sorted = [400.0, 609.0];
+    assert_eq!(
+        pair_exists_tolerant(&sorted, 2, 1000.0, Tolerance::Relative(0.01)),
+        Ok(())
+    );
+    assert_eq!(
+        pair_exists_tolerant(&sorted, 2, 1000.0, Tolerance::Relative(0.005)),
+        Err(CollapseError::NoPairFound {
+            index: 2,
+            target: 1000.0,
+            min: 400.0,
+            max: 609.0
+        })
+    );
+}
+
+#[test]
+fn negative_loads_and_differences() {
+    use namt_preventative_collapse::{collapse_check_tolerant, Tolerance};
+    //-4.0 is -1.5 + -2.5, then -3.01 is roughly -2.5 - 0.5.
+    let loads = vec![-1.5, 0.5, -2.5, -4.0, -3.01];
+    assert_eq!(
+        collapse_check_tolerant(&loads, 3, Tolerance::Absolute(0.02)),
+        Ok(())
+    );
+    assert!(collapse_check_tolerant(&loads, 3, Tolerance::Absolute(0.0)).is_err());
+}
+
+#[test]
+fn non_finite_readings_are_reported() {
+    use namt_preventative_collapse::{collapse_check_tolerant, CollapseError, Tolerance};
+    let tolerance = Tolerance::Absolute(0.1);
+    let loads = vec![1.0, f64::NAN, 2.0, 3.0];
+    let result = collapse_check_tolerant(&loads, 3, tolerance);
+    assert!(
+        matches!(result, Err(CollapseError::NotFinite { index: 1, target }) if target.is_nan())
+    );
+
+    let loads = vec![1.0, 2.0, 3.0, f64::INFINITY];
+    assert_eq!(
+        collapse_check_tolerant(&loads, 3, tolerance),
+        Err(CollapseError::NotFinite {
+            index: 3,
+            target: f64::INFINITY
+        })
+    );
+
+    //Readings are checked in order, so an earlier anomaly comes first.
+    let loads = vec![1.0, 2.0, 3.0, 10.0, f64::NEG_INFINITY];
+    assert_eq!(
+        collapse_check_tolerant(&loads, 3, tolerance)
+            .unwrap_err()
+            .index(),
+        3
+    );
+
+    //Too short to check, but still not finite.
+    let loads = vec![1.0, f64::NAN];
+    assert!(collapse_check_tolerant(&loads, 3, tolerance).is_err());
+}
+
+#[test]
+fn not_finite_display() {
+    use namt_preventative_collapse::CollapseError;
+    let error = CollapseError::NotFinite {
+        index: 2,
+        target: f64::NAN,
+    };
+    assert_eq!(
+        error.to_string(),
+        "Invalid reading: Not a finite number. Index: 2, Element: NaN"
+    );
+}
+
+#[test]
+#[should_panic]
+fn negative_tolerance_panics() {
+    use namt_preventative_collapse::{collapse_check_tolerant, Tolerance};
+    let _ = collapse_check_tolerant(&[1.0, 2.0, 3.0], 2, Tolerance::Relative(-0.1));
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::{pseudo_random_series, read};
+
+#[test]
+fn pair_rules_match_their_own_checks() {
+    use namt_preventative_collapse::{collapse_scan_rule, PairRule};
+    let numbers = read("../test_inputs/mega_mine.txt");
+    for rule in [
+        PairRule::Legacy,
+        PairRule::Strict {
+            distinct_values: false,
+        },
+        PairRule::Strict {
+            distinct_values: true,
+        },
+    ] {
+        assert_eq!(
+            collapse_scan_rule(&numbers, 100, &rule),
+            rule.collapse_scan(&numbers, 100)
+        );
+    }
+}
+
+#[test]
+fn ksum_rules_match_their_own_checks() {
+    use namt_preventative_collapse::{collapse_scan_rule, KSum};
+    for seed in 0..20 {
+        let numbers = pseudo_random_series(seed, 60, 24);
+        for k in 1..=3 {
+            for distinct in [false, true] {
+                let ksum = KSum { k, distinct };
+                assert_eq!(
+                    collapse_scan_rule(&numbers, 5, &ksum),
+                    ksum.collapse_scan(&numbers, 5)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn bounds_are_the_early_warnings_of_collapse_check() {
+    use namt_preventative_collapse::{collapse_scan, collapse_scan_rule, CollapseError};
+    use namt_preventative_collapse::{MaxBound, MinBound};
+    for seed in 0..100 {
+        let numbers = pseudo_random_series(seed, 60, 16);
+        for period in 1..6 {
+            let early_warnings: Vec<CollapseError> = collapse_scan(&numbers, period)
+                .into_iter()
+                .filter(|error| !matches!(error, CollapseError::NoPairFound { .. }))
+                .collect();
+            assert_eq!(
+                collapse_scan_rule(&numbers, period, &(MaxBound, MinBound)),
+                early_warnings
+            );
+        }
+    }
+}
+
+#[test]
+fn within_range_reports_out_of_range() {
+    use namt_preventative_collapse::{collapse_check_rule, CollapseError, WithinRange};
+    assert!(collapse_check_rule(&[3, 5, 4, 3], 3, &WithinRange).is_ok());
+    assert!(collapse_check_rule(&[3, 5, 4, 10], 3, &WithinRange).is_ok());
+    assert_eq!(
+        collapse_check_rule(&[3, 5, 4, 2], 3, &WithinRange),
+        Err(CollapseError::OutOfRange {
+            index: 3,
+            target: 2,
+            min: 3,
+            max: 5
+        })
+    );
+    assert!(collapse_check_rule(&[3, 5, 4, 11], 3, &WithinRange).is_err());
+}
+
+#[test]
+fn within_deviations_reports_outliers() {
+    use namt_preventative_collapse::{collapse_check_rule, CollapseError, WithinDeviations};
+    //Mean 0, standard deviation 2.
+    let vec: Vec<i32> = vec![-2, 2, -2, 2, 4];
+    assert!(collapse_check_rule(&vec, 4, &WithinDeviations(2.0)).is_ok());
+    let error = collapse_check_rule(&vec, 4, &WithinDeviations(1.5)).unwrap_err();
+    assert_eq!(
+        error,
+        CollapseError::Outlier {
+            index: 4,
+            target: 4,
+            mean: 0.0,
+            std_dev: 2.0
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "Imminent Failure: Reading is too far from the window mean. Index: 4, Element: 4, Window mean: 0, Standard deviation: 2"
+    );
+}
+
+#[test]
+fn rules_compose_in_order() {
+    use namt_preventative_collapse::{
+        collapse_check_rule, CollapseError, FnRule, MaxBound, PairRule, WindowRule,
+        WithinDeviations,
+    };
+    let even = FnRule(|_: &[u128], target: u128| target.is_multiple_of(2));
+    let vec = vec![1, 2, 3, 9];
+    let profile: Vec<Box<dyn WindowRule>> = vec![
+        Box::new(even),
+        Box::new(MaxBound),
+        Box::new(PairRule::Legacy),
+    ];
+    assert_eq!(
+        collapse_check_rule(&vec, 3, &profile),
+        Err(CollapseError::Rejected {
+            index: 3,
+            target: 9,
+            min: 1,
+            max: 3
+        })
+    );
+    assert_eq!(
+        collapse_check_rule(&vec, 3, &(MaxBound, even)),
+        Err(CollapseError::MaxTooSmall {
+            index: 3,
+            target: 9,
+            min: 1,
+            max: 3
+        })
+    );
+    let vec = vec![1, 2, 3, 5];
+    assert!(collapse_check_rule(&vec, 3, &(MaxBound, PairRule::Legacy)).is_ok());
+    assert!(collapse_check_rule(
+        &vec,
+        3,
+        &(MaxBound, PairRule::Legacy, WithinDeviations(1.0))
+    )
+    .is_err());
+}
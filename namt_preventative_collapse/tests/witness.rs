@@ -0,0 +1,87 @@
+mod common;
+
+use common::pseudo_random_signed_series;
+
+#[test]
+fn witnesses_point_into_the_window() {
+    use namt_preventative_collapse::collapse_witnesses;
+    use namt_preventative_collapse::parse_readings;
+    use std::fs;
+    let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
+    let period = 100;
+    let numbers = &numbers[..5231]; //The first anomaly is at 5231.
+
+    let witnesses = collapse_witnesses(numbers, period).unwrap();
+    assert_eq!(witnesses.len(), numbers.len() - period);
+    for (c, witness) in (period..).zip(witnesses.iter()) {
+        assert_eq!(witness.index, c);
+        assert_eq!(witness.target, numbers[c]);
+        assert!((c - period..c).contains(&witness.a_index));
+        assert!((c - period..c).contains(&witness.b_index));
+        assert_eq!(numbers[witness.a_index], witness.a);
+        assert_eq!(numbers[witness.b_index], witness.b);
+        match witness.is_sum() {
+            true => assert_eq!(witness.a + witness.b, witness.target),
+            false => assert_eq!(witness.a - witness.b, witness.target),
+        }
+    }
+}
+
+#[test]
+fn witnesses_stop_at_the_first_anomaly() {
+    use namt_preventative_collapse::{collapse_check, collapse_witnesses};
+    let vec = vec![2, 1, 3, 5, 4, 7, 13];
+    assert_eq!(
+        collapse_witnesses(&vec, 3),
+        Err(collapse_check(&vec, 3).unwrap_err())
+    );
+}
+
+#[test]
+fn witness_report_has_one_row_per_reading() {
+    use namt_preventative_collapse::{collapse_witnesses, write_witness_report};
+    let vec = vec![2, 1, 3, 5, 4, 7];
+    let witnesses = collapse_witnesses(&vec, 3).unwrap();
+    let mut report = vec![];
+    write_witness_report(&witnesses, &mut report).unwrap();
+    let report = String::from_utf8(report).unwrap();
+    assert_eq!(
+        report.lines().collect::<Vec<_>>(),
+        vec![
+            "index,target,a_index,a,b_index,b",
+            "3,5,0,2,2,3",
+            "4,4,1,1,2,3",
+            "5,7,2,3,4,4"
+        ]
+    );
+}
+
+#[test]
+fn signed_witnesses_point_into_the_window() {
+    use namt_preventative_collapse::{collapse_check, collapse_witnesses, Report, Strategy};
+    let period = 4;
+    for seed in 0..50 {
+        let numbers = pseudo_random_signed_series(seed, 80, 11, true);
+        let end = match collapse_check(&numbers, period) {
+            Ok(()) => numbers.len(),
+            Err(error) => error.index(),
+        };
+        let numbers = &numbers[..end];
+
+        let witnesses = collapse_witnesses(numbers, period).unwrap();
+        for (c, witness) in (period..).zip(witnesses.iter()) {
+            assert_eq!(witness.target, numbers[c]);
+            assert_eq!(numbers[witness.a_index], witness.a);
+            assert_eq!(numbers[witness.b_index], witness.b);
+            match witness.is_sum() {
+                true => assert_eq!(witness.a + witness.b, witness.target),
+                false => assert_eq!(witness.a - witness.b, witness.target),
+            }
+        }
+        let report = Report::new(numbers, period, Strategy::Resort, true).with_witnesses(numbers);
+        assert!(report.summary.safe);
+        assert_eq!(report.summary.min_reading, numbers.iter().min().copied());
+        assert_eq!(report.witnesses, witnesses);
+    }
+}
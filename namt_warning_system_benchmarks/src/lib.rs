@@ -1,53 +1,198 @@
 #![feature(test)]
 
 extern crate test;
+#[cfg(test)]
 use test::Bencher;
+#[cfg(test)]
 fn setup() -> (Vec<u128>, usize) {
+    setup_with_period(100)
+}
+#[cfg(test)]
+fn setup_with_period(period: usize) -> (Vec<u128>, usize) {
+    use namt_preventative_collapse::parse_readings;
     use std::fs;
     let numbers = fs::read_to_string("../test_inputs/mega_mine.txt");
-    let numbers = Vec::from_iter(numbers.unwrap().lines().map(|x| x.parse::<u128>().unwrap()));
-    (numbers, 100)
+    let numbers = parse_readings::<u128>(&numbers.unwrap()).unwrap();
+    (numbers, period)
+}
+///Benches any strategy at compile time, over `mega_mine.txt` with the given period.
+#[cfg(test)]
+fn bench_with<S: namt_preventative_collapse::CollapseStrategy<Reading = u128>>(
+    bencher: &mut Bencher,
+    period: usize,
+) {
+    let (numbers, period) = setup_with_period(period);
+    use namt_preventative_collapse::collapse_check_with;
+    bencher.iter(|| collapse_check_with::<S>(&numbers, period));
 }
 #[bench]
 fn namt_current(bencher: &mut Bencher) {
-    let (mut numbers, period) = setup();
+    let (numbers, period) = setup();
     use namt_preventative_collapse::collapse_check;
-    bencher.iter(|| collapse_check(&mut numbers, period));
+    bencher.iter(|| collapse_check(&numbers, period));
 }
 #[bench]
 #[ignore]
 fn namt_verify_many_allocations_is_slow(bencher: &mut Bencher) {
-    let (mut numbers, period) = setup();
+    let (numbers, period) = setup();
     use namt_preventative_collapse::bench_iterations::collapse_check_alloc_and_sort_on_each_loop;
-    bencher.iter(|| collapse_check_alloc_and_sort_on_each_loop(&mut numbers, period));
+    bencher.iter(|| collapse_check_alloc_and_sort_on_each_loop(&numbers, period));
 }
 
 #[bench]
 //Not really. It's slower on average, with a wider standard deviation. But the developer experience was nicer.
 fn namt_are_references_better(bencher: &mut Bencher) {
-    let (mut numbers, period) = setup();
+    let (numbers, period) = setup();
     use namt_preventative_collapse::bench_iterations::collapse_check_are_references_better;
-    bencher.iter(|| collapse_check_are_references_better(&mut numbers, period));
+    bencher.iter(|| collapse_check_are_references_better(&numbers, period));
 }
 
 //Nope
 #[bench]
 fn more_short_circuit(bencher: &mut Bencher) {
-    let (mut numbers, period) = setup();
+    let (numbers, period) = setup();
     use namt_preventative_collapse::bench_iterations::collapse_check_with_more_short_circuiting;
-    bencher.iter(|| collapse_check_with_more_short_circuiting(&mut numbers, period));
+    bencher.iter(|| collapse_check_with_more_short_circuiting(&numbers, period));
 }
 //uh-uh
 #[bench]
 fn namt_local_alloc_with_no_short_circuit(bencher: &mut Bencher) {
-    let (mut numbers, period) = setup();
+    let (numbers, period) = setup();
     use namt_preventative_collapse::bench_iterations::collapse_check_no_short_ciruit;
-    bencher.iter(|| collapse_check_no_short_ciruit(&mut numbers, period));
+    bencher.iter(|| collapse_check_no_short_ciruit(&numbers, period));
 }
 //uh-uh
 #[bench]
 fn namt_narrowing_binary_search(bencher: &mut Bencher) {
-    let (mut numbers, period) = setup();
+    let (numbers, period) = setup();
     use namt_preventative_collapse::bench_iterations::collapse_check_narrowing_search;
-    bencher.iter(|| collapse_check_narrowing_search(&mut numbers, period));
+    bencher.iter(|| collapse_check_narrowing_search(&numbers, period));
+}
+
+//Re-sorted window against a counted window and an incrementally sorted window, as the period grows.
+//At period 25 both stop at the first anomaly, index 48, so that pair mostly measures setup.
+#[bench]
+fn namt_current_period_25(bencher: &mut Bencher) {
+    let (numbers, period) = setup_with_period(25);
+    use namt_preventative_collapse::collapse_check;
+    bencher.iter(|| collapse_check(&numbers, period));
+}
+#[bench]
+fn namt_hashed_period_25(bencher: &mut Bencher) {
+    use namt_preventative_collapse::HashedWindow;
+    bench_with::<HashedWindow>(bencher, 25);
+}
+#[bench]
+fn namt_incremental_period_25(bencher: &mut Bencher) {
+    use namt_preventative_collapse::IncrementalWindow;
+    bench_with::<IncrementalWindow>(bencher, 25);
+}
+#[bench]
+fn namt_current_period_100(bencher: &mut Bencher) {
+    let (numbers, period) = setup_with_period(100);
+    use namt_preventative_collapse::collapse_check;
+    bencher.iter(|| collapse_check(&numbers, period));
+}
+#[bench]
+fn namt_hashed_period_100(bencher: &mut Bencher) {
+    use namt_preventative_collapse::HashedWindow;
+    bench_with::<HashedWindow>(bencher, 100);
+}
+#[bench]
+fn namt_incremental_period_100(bencher: &mut Bencher) {
+    use namt_preventative_collapse::IncrementalWindow;
+    bench_with::<IncrementalWindow>(bencher, 100);
+}
+#[bench]
+fn namt_current_period_1000(bencher: &mut Bencher) {
+    let (numbers, period) = setup_with_period(1000);
+    use namt_preventative_collapse::collapse_check;
+    bencher.iter(|| collapse_check(&numbers, period));
+}
+#[bench]
+fn namt_hashed_period_1000(bencher: &mut Bencher) {
+    use namt_preventative_collapse::HashedWindow;
+    bench_with::<HashedWindow>(bencher, 1000);
+}
+#[bench]
+fn namt_incremental_period_1000(bencher: &mut Bencher) {
+    use namt_preventative_collapse::IncrementalWindow;
+    bench_with::<IncrementalWindow>(bencher, 1000);
+}
+#[bench]
+fn namt_current_period_10000(bencher: &mut Bencher) {
+    let (numbers, period) = setup_with_period(10000);
+    use namt_preventative_collapse::collapse_check;
+    bencher.iter(|| collapse_check(&numbers, period));
+}
+#[bench]
+fn namt_hashed_period_10000(bencher: &mut Bencher) {
+    use namt_preventative_collapse::HashedWindow;
+    bench_with::<HashedWindow>(bencher, 10000);
+}
+#[bench]
+fn namt_incremental_period_10000(bencher: &mut Bencher) {
+    use namt_preventative_collapse::IncrementalWindow;
+    bench_with::<IncrementalWindow>(bencher, 10000);
+}
+#[bench]
+fn namt_tolerant_period_100(bencher: &mut Bencher) {
+    let (numbers, period) = setup_with_period(100);
+    let loads: Vec<f64> = numbers.iter().map(|x| *x as f64).collect();
+    use namt_preventative_collapse::{collapse_check_tolerant, Tolerance};
+    bencher.iter(|| collapse_check_tolerant(&loads, period, Tolerance::Relative(1e-12)));
+}
+#[bench]
+fn namt_tolerant_period_1000(bencher: &mut Bencher) {
+    let (numbers, period) = setup_with_period(1000);
+    let loads: Vec<f64> = numbers.iter().map(|x| *x as f64).collect();
+    use namt_preventative_collapse::{collapse_check_tolerant, Tolerance};
+    bencher.iter(|| collapse_check_tolerant(&loads, period, Tolerance::Relative(1e-12)));
+}
+#[cfg(test)]
+fn bench_ksum(bencher: &mut Bencher, k: usize, distinct: bool) {
+    let (numbers, period) = setup();
+    use namt_preventative_collapse::KSum;
+    bencher.iter(|| KSum { k, distinct }.collapse_scan(&numbers, period));
+}
+#[bench]
+fn namt_ksum_1_period_100(bencher: &mut Bencher) {
+    bench_ksum(bencher, 1, false);
+}
+#[bench]
+fn namt_ksum_2_period_100(bencher: &mut Bencher) {
+    bench_ksum(bencher, 2, true);
+}
+#[bench]
+fn namt_ksum_3_period_100(bencher: &mut Bencher) {
+    bench_ksum(bencher, 3, true);
+}
+#[bench]
+fn namt_ksum_3_repeats_period_100(bencher: &mut Bencher) {
+    bench_ksum(bencher, 3, false);
+}
+///A generated series without anomalies, so every strategy walks all of it instead of stopping at the first.
+#[cfg(test)]
+fn bench_generated<S: namt_preventative_collapse::CollapseStrategy<Reading = u128>>(
+    bencher: &mut Bencher,
+    period: usize,
+) {
+    use namt_preventative_collapse::{collapse_check_with, Generator};
+    let numbers = Generator::new(period, 20_000).generate().readings;
+    bencher.iter(|| collapse_check_with::<S>(&numbers, period));
+}
+#[bench]
+fn namt_current_generated_period_100(bencher: &mut Bencher) {
+    use namt_preventative_collapse::ResortWindow;
+    bench_generated::<ResortWindow>(bencher, 100);
+}
+#[bench]
+fn namt_hashed_generated_period_100(bencher: &mut Bencher) {
+    use namt_preventative_collapse::HashedWindow;
+    bench_generated::<HashedWindow>(bencher, 100);
+}
+#[bench]
+fn namt_incremental_generated_period_100(bencher: &mut Bencher) {
+    use namt_preventative_collapse::IncrementalWindow;
+    bench_generated::<IncrementalWindow>(bencher, 100);
 }